@@ -0,0 +1,61 @@
+use std::io::{self, Write};
+
+use error_stack::{IntoReport, ResultExt};
+use serde::Serialize;
+
+use crate::{
+    app::AppError,
+    client::elasticsearch::{ElasticsearchClient, ElasticsearchClientError},
+    config::Config,
+};
+
+/// Fetch the resource of the cluster once and print it as json to stdout.
+pub(super) async fn run(
+    config: Config,
+    cluster_name: &str,
+    resource: &str,
+) -> error_stack::Result<(), AppError> {
+    let client_config = config
+        .elasticsearch
+        .unwrap_or_default()
+        .into_iter()
+        .find(|c| c.name == cluster_name)
+        .ok_or_else(|| error_stack::report!(AppError::ConfigureClient))
+        .attach_printable_lazy(|| format!("cluster {cluster_name} not found in config"))?;
+
+    let client = ElasticsearchClient::new(client_config)
+        .await
+        .change_context(AppError::ConfigureClient)?;
+
+    // same names as the elasticsearch resources in the tui.
+    match resource {
+        "cluster" => print_json(client.get_cluster_health().await),
+        "index" => print_json(client.cat_indices(None).await),
+        "node" => print_json(client.cat_nodes().await),
+        "alias" => print_json(client.cat_aliases().await),
+        "template" => print_json(client.get_index_templates().await),
+        "ilm" => print_json(client.get_ilm_policies().await),
+        "snapshot" => print_json(client.get_repositories().await),
+        "settings" => print_json(client.get_cluster_settings().await),
+        "task" => print_json(client.get_tasks().await),
+        _ => Err(error_stack::report!(AppError::FetchResource))
+            .attach_printable(format!("unknown resource {resource}")),
+    }
+}
+
+fn print_json<T>(
+    response: error_stack::Result<T, ElasticsearchClientError>,
+) -> error_stack::Result<(), AppError>
+where
+    T: Serialize,
+{
+    let response = response.change_context(AppError::FetchResource)?;
+
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &response)
+        .into_report()
+        .change_context(AppError::FetchResource)?;
+    writeln!(stdout)
+        .into_report()
+        .change_context(AppError::FetchResource)
+}
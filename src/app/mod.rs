@@ -1,21 +1,53 @@
-use error_stack::{IntoReport, ResultExt};
+use std::{
+    collections::hash_map::RandomState,
+    fs::OpenOptions,
+    hash::{BuildHasher, Hasher},
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use error_stack::{AttachmentKind, FrameKind, IntoReport, ResultExt};
 use futures::future::OptionFuture;
 use thiserror::Error;
+use tokio::time::MissedTickBehavior;
+use tracing_subscriber::fmt::format::FmtSpan;
 pub(crate) use transport::{RequestId, TransportResult, TransportStats};
 
 use crate::{
     app::transport::TransportController,
-    config::Config,
-    event::input::{self, Command, InputHandler},
-    terminal::TerminalGuard,
-    view::View,
+    config::{Config, SessionState},
+    event::{
+        api::ApiHandler,
+        input::{self, Command, InputHandler},
+    },
+    terminal::{self, TerminalGuard},
+    view::{component::help::SPINNER_INTERVAL, View},
 };
 
+mod headless;
 mod transport;
 
 pub struct App {
     config: Config,
-    terminal: TerminalGuard,
+    mode: AppMode,
+}
+
+/// Minimum interval between redraws, inputs and responses in between are drawn together.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Interval to poll the cluster health in the follow mode.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Fraction of the follow interval to spread the polls by.
+const DEFAULT_POLL_JITTER: f64 = 0.1;
+
+/// How the app presents the data.
+pub enum AppMode {
+    /// Interactive terminal ui.
+    Tui(TerminalGuard),
+    /// Fetch the resource of the cluster once and print it as json, without touching the terminal.
+    Json { cluster: String, resource: String },
 }
 
 #[derive(Debug, Error)]
@@ -24,27 +56,66 @@ pub enum AppError {
     TerminalIo,
     #[error("configure client error")]
     ConfigureClient,
+    #[error("initialize logging error")]
+    InitLogging,
+    #[error("preflight check error")]
+    Preflight,
+    #[error("fetch resource error")]
+    FetchResource,
 }
 
 impl App {
     pub fn new(config: Config, terminal: TerminalGuard) -> Self {
-        Self { config, terminal }
+        Self::with_mode(config, AppMode::Tui(terminal))
+    }
+
+    pub fn with_mode(config: Config, mode: AppMode) -> Self {
+        Self { config, mode }
     }
 
     pub async fn run(self) -> error_stack::Result<(), AppError> {
-        let App {
-            config,
-            mut terminal,
-        } = self;
+        let App { config, mode } = self;
+
+        if let Some(log_file) = config.log_file.as_deref() {
+            init_log_file(log_file)?;
+        }
+
+        match mode {
+            AppMode::Tui(terminal) => Self::run_tui(config, terminal).await,
+            AppMode::Json { cluster, resource } => {
+                headless::run(config, cluster.as_str(), resource.as_str()).await
+            }
+        }
+    }
+
+    async fn run_tui(
+        config: Config,
+        mut terminal: TerminalGuard,
+    ) -> error_stack::Result<(), AppError> {
+        let api_handler = ApiHandler::new(config.elasticsearch.clone().unwrap_or_default()).await;
+        Self::preflight(&config, &api_handler).await?;
+
+        let state_file = config.state_file.clone();
+        let config = match state_file.as_deref().and_then(SessionState::load) {
+            Some(state) => config.with_session_state(state),
+            None => config,
+        };
 
         terminal
             .clear()
             .into_report()
             .change_context(AppError::TerminalIo)?;
 
+        let config_source = config.source.clone();
+        let mut poll_jitter = config.poll_jitter.unwrap_or(DEFAULT_POLL_JITTER);
+        // reloaded config waiting for in-flight requests to finish.
+        let mut pending_config: Option<Config> = None;
+
         let mut input = InputHandler::new(input::EventStream::new());
-        let mut transport = TransportController::init(config.clone())?;
-        let mut view = View::new(config).with_transport_stats(transport.stats());
+        let mut transport = TransportController::init(api_handler);
+        let mut view = View::new(config)
+            .with_transport_stats(transport.stats())
+            .with_build_failures(transport.build_failures());
 
         OptionFuture::from(
             view.pre_render_loop()
@@ -52,38 +123,319 @@ impl App {
         )
         .await;
 
+        let mut last_draw: Option<Instant> = None;
+        let mut dirty = true;
+
+        let mut next_follow = Instant::now();
+        let mut spinner_timer = tokio::time::interval(SPINNER_INTERVAL);
+        spinner_timer.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
         loop {
-            terminal
-                .draw(|f| view.render(f, f.size()))
-                .into_report()
-                .change_context_lazy(|| AppError::TerminalIo)?;
+            let since_last_draw = last_draw.map(|at| at.elapsed());
+            if dirty && since_last_draw.unwrap_or(REDRAW_INTERVAL) >= REDRAW_INTERVAL {
+                terminal
+                    .draw(|f| view.render(f, f.size()))
+                    .into_report()
+                    .change_context_lazy(|| AppError::TerminalIo)?;
+                last_draw = Some(Instant::now());
+                dirty = false;
+            }
+            let until_next_draw =
+                REDRAW_INTERVAL.saturating_sub(since_last_draw.unwrap_or(REDRAW_INTERVAL));
+            let highlight_remaining = view.highlight_remaining();
+            let until_next_follow = next_follow.saturating_duration_since(Instant::now());
 
             tokio::select! {
                 biased; // tokio::select macro feature.
 
-                command = input.read(view.state()) => match command {
+                command = input.read(view.state()) => match command.into_report().change_context(AppError::TerminalIo)? {
                     Command::QuitApp => break,
                     Command::UnfocusComponent => view.unfocus(),
-                    Command::FocusComponent(component) => view.focus(component),
+                    Command::FocusComponent(component) => {
+                        OptionFuture::from(view.focus(component).map(|events| transport.send_requests(events))).await;
+                    }
                     Command::NavigateComponent(component, navigate) => {
                         OptionFuture::from(view.navigate_component(component,navigate).map(|events| transport.send_requests(events))).await;
                     }
+                    Command::EnterComponent(component) => {
+                        OptionFuture::from(view.enter(component).map(|events| transport.send_requests(events))).await;
+                    }
+                    Command::LeaveComponent => view.leave(),
+                    Command::ToggleLeftPanel => view.toggle_left_panel(),
+                    Command::CycleFocus(reverse) => {
+                        OptionFuture::from(view.focus_next(reverse).map(|events| transport.send_requests(events))).await;
+                    }
+                    Command::ToggleAbout => view.toggle_about(),
+                    Command::CycleHelpBar => view.cycle_help_bar(),
+                    Command::CycleFilterPreset(component) => {
+                        OptionFuture::from(view.cycle_filter_preset(component).map(|events| transport.send_requests(events))).await;
+                    }
+                    Command::ToggleDensity => view.toggle_density(),
+                    Command::ToggleHiddenIndices => view.toggle_hidden_indices(),
+                    Command::ToggleRedact => view.toggle_redact(),
+                    Command::ToggleSizeScope => view.toggle_size_scope(),
+                    Command::CycleByteDisplay => view.cycle_byte_display(),
+                    Command::ToggleCompactJson => view.toggle_compact_json(),
+                    Command::ToggleRelativeTimestamps => view.toggle_relative_timestamps(),
+                    Command::ToggleIndexGroups => view.toggle_index_groups(),
+                    Command::ToggleFanOut => view.toggle_fan_out(),
+                    Command::ToggleHealthLog => view.toggle_health_log(),
+                    Command::ToggleFollow => view.toggle_follow(),
+                    Command::ResetView => {
+                        OptionFuture::from(view.reset().map(|events| transport.send_requests(events))).await;
+                    }
+                    Command::LoadMore => {
+                        OptionFuture::from(view.load_more().map(|events| transport.send_requests(events))).await;
+                    }
+                    Command::CycleSort(component) => view.cycle_sort(component),
+                    Command::AdjustColumn(component, adjust) => view.adjust_column(component, adjust),
+                    Command::ToggleExpand(component) => {
+                        OptionFuture::from(view.toggle_expand(component).map(|events| transport.send_requests(events))).await;
+                    }
+                    Command::JumpToInitial(component, initial) => {
+                        OptionFuture::from(view.jump_to_initial(component, initial).map(|events| transport.send_requests(events))).await;
+                    }
+                    Command::OpenInBrowser => match view.selected_url() {
+                        Some(url) => {
+                            if let Err(report) = terminal::open_in_browser(url.as_str()) {
+                                tracing::error!("{report:?}");
+                                view.notify("failed to open browser");
+                            }
+                        }
+                        None => view.notify("no index selected"),
+                    },
+                    Command::CopyError => {
+                        match transport.stats().latest_transport().as_ref().and_then(|t| t.format_err_msg()) {
+                            Some(message) => match terminal::copy_to_clipboard(message) {
+                                Ok(()) => view.notify("error copied"),
+                                Err(report) => tracing::error!("{report:?}"),
+                            },
+                            None => view.notify("no error to copy"),
+                        }
+                    }
+                    Command::CopyCurl => match view.selected_curl() {
+                        Some(curl) => match terminal::copy_to_clipboard(curl.as_str()) {
+                            Ok(()) => view.notify("curl copied"),
+                            Err(report) => tracing::error!("{report:?}"),
+                        },
+                        None => view.notify("no request to copy"),
+                    },
+                    Command::CopyPath => match view.selected_path() {
+                        Some(path) => match terminal::copy_to_clipboard(path.as_str()) {
+                            Ok(()) => view.notify(format!("copied {path}")),
+                            Err(report) => tracing::error!("{report:?}"),
+                        },
+                        None => view.notify("no request to copy"),
+                    },
+                    Command::EditComponent(component, edit) => {
+                        OptionFuture::from(view.edit_component(component, edit).map(|events| transport.send_requests(events))).await;
+                    }
+                    Command::ReloadConfig => {
+                        if let Some(config) = reload_config(config_source.as_deref(), &mut view) {
+                            pending_config = Some(config);
+                        }
+                    }
+                    Command::EditConfig => match config_source.as_deref() {
+                        Some(path) => {
+                            // stop reading the inputs while the editor has the terminal.
+                            drop(input);
+                            let edited = terminal
+                                .suspend(|| terminal::edit_file(path))
+                                .change_context(AppError::TerminalIo)?;
+                            input = InputHandler::new(input::EventStream::new());
+
+                            match edited {
+                                Ok(()) => {
+                                    if let Some(config) = reload_config(Some(path), &mut view) {
+                                        pending_config = Some(config);
+                                    }
+                                }
+                                Err(report) => {
+                                    tracing::error!("{report:?}");
+                                    view.notify(format!("edit config: {}", error_reason(&report)));
+                                }
+                            }
+                        }
+                        None => view.notify("config is not loaded from a file"),
+                    },
                 },
 
                 Some(res) = transport.recv_response() => {
                     match res.result {
                         Ok(event) => {
                             tracing::debug!(?event, "Receive api response");
-                            view.update_api_response(event);
+                            OptionFuture::from(view.update_api_response(event).map(|events| transport.send_requests(events))).await;
+                            for cluster_name in view.take_turned_red() {
+                                if let Err(report) = terminal::ring_bell() {
+                                    tracing::error!("{report:?}");
+                                }
+                                view.notify(format!("{cluster_name} turned red"));
+                            }
                         }
                         Err(report) => {
                            tracing::error!(request_id=?res.request_id, "{report:?}");
+                           view.update_api_error(res.request, error_reason(&report));
                         }
                     }
                 }
+
+                _ = tokio::time::sleep(until_next_follow), if view.is_following() => {
+                    next_follow = Instant::now() + jittered(FOLLOW_INTERVAL, poll_jitter);
+                    OptionFuture::from(view.follow_tick().map(|events| transport.send_requests(events))).await;
+                }
+
+                // redraw to advance the spinner.
+                _ = spinner_timer.tick(), if view.animations() && transport.has_in_flight() => (),
+
+                // redraw to fade the highlighted changes.
+                _ = tokio::time::sleep(highlight_remaining.unwrap_or_default()), if highlight_remaining.is_some() => (),
+
+                // draw the last change once the interval has passed.
+                _ = tokio::time::sleep(until_next_draw), if dirty => (),
+            }
+            // every branch changes the view or is waiting for the pending draw.
+            dirty = true;
+
+            // swap clients after in-flight responses are applied to the current view.
+            if !transport.has_in_flight() {
+                if let Some(config) = pending_config.take() {
+                    poll_jitter = config.poll_jitter.unwrap_or(DEFAULT_POLL_JITTER);
+                    let elasticsearch = config.elasticsearch.clone().unwrap_or_default();
+                    transport.reload(ApiHandler::new(elasticsearch).await);
+                    view = View::new(config)
+                        .with_transport_stats(transport.stats())
+                        .with_build_failures(transport.build_failures());
+                    OptionFuture::from(
+                        view.pre_render_loop()
+                            .map(|events| transport.send_requests(events)),
+                    )
+                    .await;
+                    view.notify("config reloaded");
+                }
+            }
+        }
+        transport.shutdown();
+
+        if let Some(state_file) = state_file {
+            if let Err(report) = view.session_state().save(state_file) {
+                tracing::error!("{report:?}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Check each cluster is reachable before rendering, so that misconfiguration is noticed early.
+    async fn preflight(
+        config: &Config,
+        api_handler: &ApiHandler,
+    ) -> error_stack::Result<(), AppError> {
+        let preflight = config.preflight.clone().unwrap_or_default();
+        if preflight.skip {
+            return Ok(());
+        }
+
+        // the build error is logged when the client is built.
+        let mut failed: Vec<String> = api_handler.build_failures().into_keys().collect();
+        for (name, result) in api_handler.ping().await {
+            match result {
+                Ok(()) => tracing::info!(cluster_name = %name, "Cluster reachable"),
+                Err(report) => {
+                    tracing::warn!(cluster_name = %name, "Cluster unreachable {report:?}");
+                    failed.push(name);
+                }
             }
         }
+        failed.sort();
+
+        if preflight.strict && !failed.is_empty() {
+            return Err(error_stack::report!(AppError::Preflight))
+                .attach_printable(format!("unreachable clusters: {}", failed.join(", ")));
+        }
 
         Ok(())
     }
 }
+
+/// Spread the interval randomly by the fraction in both directions.
+fn jittered(interval: Duration, jitter: f64) -> Duration {
+    // nan would panic on mul_f64.
+    let jitter = if jitter.is_finite() {
+        jitter.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    if jitter == 0.0 {
+        return interval;
+    }
+    // every RandomState is seeded differently, which is random enough to spread the polls.
+    let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+    interval.mul_f64(1.0 + jitter * (random * 2.0 - 1.0))
+}
+
+/// Load the config from the file again, the failure is notified and `None` is returned.
+fn reload_config(source: Option<&Path>, view: &mut View) -> Option<Config> {
+    match source.map(Config::from_path) {
+        Some(Ok(config)) => Some(config),
+        Some(Err(report)) => {
+            tracing::error!("{report:?}");
+            view.notify(format!("reload config: {}", error_reason(&report)));
+            None
+        }
+        None => {
+            view.notify("config is not loaded from a file");
+            None
+        }
+    }
+}
+
+/// Root cause of the error, which is more specific than the top level context, with the
+/// messages attached to it like the failed field of the response.
+pub(crate) fn error_reason<C>(report: &error_stack::Report<C>) -> String {
+    let mut reason = String::new();
+    // frames are iterated from the top, so attachments come before their context.
+    let mut attachments = Vec::new();
+    let mut details = Vec::new();
+    for frame in report.frames() {
+        match frame.kind() {
+            FrameKind::Context(context) => {
+                reason = context.to_string();
+                details = std::mem::take(&mut attachments);
+            }
+            FrameKind::Attachment(AttachmentKind::Printable(printable)) => {
+                attachments.push(printable.to_string());
+            }
+            FrameKind::Attachment(_) => {}
+        }
+    }
+    if details.is_empty() {
+        reason
+    } else {
+        format!("{reason}: {}", details.join(", "))
+    }
+}
+
+/// Write json lines log to the file instead of the terminal which is used for rendering.
+fn init_log_file(path: &Path) -> error_stack::Result<(), AppError> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .into_report()
+        .change_context(AppError::InitLogging)
+        .attach_printable_lazy(|| format!("open {}", path.display()))?;
+
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_ansi(false)
+        .with_max_level(tracing::Level::DEBUG)
+        // request timings are recorded to the span.
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(Mutex::new(file))
+        .finish();
+
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        tracing::warn!("Global subscriber already installed, log file is ignored");
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, VecDeque},
+    fmt::{self, Display},
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc, RwLock,
@@ -7,24 +8,31 @@ use std::{
     time::{Duration, Instant},
 };
 
-use error_stack::ResultExt;
+use error_stack::Report;
 use tokio::sync::mpsc::{self, Receiver, Sender};
 
-use crate::{
-    app::AppError,
-    config::Config,
-    event::api::{
-        ApiHandleError, ApiHandler, RequestEnvelope, RequestEvent, ResponseEnvelope, ResponseEvent,
-    },
+use crate::event::api::{
+    ApiHandleError, ApiHandler, ChannelClosed, RequestEnvelope, RequestEvent, ResponseEnvelope,
+    ResponseEvent, RetryAttempts,
 };
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub(crate) struct RequestId(u64);
 
+/// Same notation in the help bar and the log file to correlate them.
+impl Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct TransportResult {
+    pub(crate) request_id: RequestId,
     pub(crate) _request: RequestEvent,
     pub(crate) response: std::result::Result<ResponseEvent, ApiHandleError>,
+    /// Formatted error report including attachments.
+    error_report: Option<String>,
     request_send: Instant,
     response_received: Instant,
 }
@@ -33,12 +41,19 @@ impl TransportResult {
     pub(crate) fn elapsed(&self) -> Duration {
         self.response_received.duration_since(self.request_send)
     }
+
+    /// Full error message of the failed request.
+    pub(crate) fn format_err_msg(&self) -> Option<&str> {
+        self.error_report.as_deref()
+    }
 }
 
 #[derive(Debug, Default)]
 pub(crate) struct TransportStats {
     pub(crate) in_flight_requests: AtomicUsize,
     history: RwLock<VecDeque<TransportResult>>,
+    /// Attempts of the in-flight requests being retried.
+    retries: RetryAttempts,
 }
 
 impl TransportStats {
@@ -48,6 +63,13 @@ impl TransportStats {
     pub(crate) fn latest_transport(&self) -> Option<TransportResult> {
         self.history.read().unwrap().front().cloned()
     }
+
+    /// Attempt and the max attempts of the furthest retrying request.
+    pub(crate) fn retrying(&self) -> Option<(u32, u32)> {
+        self.retries
+            .max_attempt()
+            .map(|attempt| (attempt, ApiHandler::MAX_ATTEMPTS))
+    }
 }
 
 pub(super) struct TransportController {
@@ -56,26 +78,78 @@ pub(super) struct TransportController {
     stats: Arc<TransportStats>,
     in_flights: HashMap<RequestId, (Instant, RequestEvent)>,
     next_request_id: RequestId,
+    /// Clusters whose client failed to build with the reason.
+    build_failures: HashMap<String, String>,
 }
 
 impl TransportController {
     const HISTORY_SIZE: usize = 100;
 
-    pub(super) fn init(config: Config) -> error_stack::Result<Self, AppError> {
-        let (req_tx, req_rx) = mpsc::channel::<RequestEnvelope>(10);
-        let (res_tx, res_rx) = mpsc::channel::<ResponseEnvelope>(10);
-        let api_handler = ApiHandler::new(config.elasticsearch.unwrap_or_default())
-            .change_context_lazy(|| AppError::ConfigureClient)?;
-
-        tokio::spawn(api_handler.run(req_rx, res_tx));
+    pub(super) fn init(api_handler: ApiHandler) -> Self {
+        let stats = Arc::new(TransportStats::new());
+        let (req_tx, res_rx, build_failures) =
+            Self::spawn_handler(api_handler, stats.retries.clone());
 
-        Ok(Self {
+        Self {
             req_tx,
             res_rx,
-            stats: Arc::new(TransportStats::new()),
+            stats,
             in_flights: HashMap::new(),
             next_request_id: RequestId(0),
-        })
+            build_failures,
+        }
+    }
+
+    /// Replace the api handler with the one built from the reloaded config.
+    /// Responses of in-flight requests are lost, so they should be drained first.
+    pub(super) fn reload(&mut self, api_handler: ApiHandler) {
+        let (req_tx, res_rx, build_failures) =
+            Self::spawn_handler(api_handler, self.stats.retries.clone());
+        // the previous handler stops when its request channel is closed.
+        self.req_tx = req_tx;
+        self.res_rx = res_rx;
+        self.build_failures = build_failures;
+        self.in_flights.clear();
+        self.stats.in_flight_requests.store(0, Ordering::Relaxed);
+        self.stats.retries.clear();
+    }
+
+    pub(super) fn build_failures(&self) -> HashMap<String, String> {
+        self.build_failures.clone()
+    }
+
+    /// Stop the api handler. In-flight requests fail to send their responses instead of waiting
+    /// for them to be received.
+    pub(super) fn shutdown(mut self) {
+        self.res_rx.close();
+        if !self.in_flights.is_empty() {
+            tracing::info!(
+                in_flights = self.in_flights.len(),
+                "Drop in-flight requests"
+            );
+        }
+    }
+
+    pub(super) fn has_in_flight(&self) -> bool {
+        !self.in_flights.is_empty()
+    }
+
+    fn spawn_handler(
+        api_handler: ApiHandler,
+        retries: RetryAttempts,
+    ) -> (
+        Sender<RequestEnvelope>,
+        Receiver<ResponseEnvelope>,
+        HashMap<String, String>,
+    ) {
+        let (req_tx, req_rx) = mpsc::channel::<RequestEnvelope>(10);
+        let (res_tx, res_rx) = mpsc::channel::<ResponseEnvelope>(10);
+        let api_handler = api_handler.with_retries(retries);
+        let build_failures = api_handler.build_failures();
+
+        tokio::spawn(api_handler.run(req_rx, res_tx));
+
+        (req_tx, res_rx, build_failures)
     }
 
     pub(super) async fn send_requests(&mut self, reqs: impl Iterator<Item = RequestEvent>) {
@@ -92,13 +166,23 @@ impl TransportController {
             .in_flight_requests
             .store(self.in_flights.len(), Ordering::Relaxed);
 
-        self.req_tx
+        let sent = self
+            .req_tx
             .send(RequestEnvelope {
                 request_id,
                 event: req,
             })
-            .await
-            .ok();
+            .await;
+        // the response never comes, do not wait for it.
+        if sent.is_err() {
+            self.in_flights.remove(&request_id);
+            self.stats
+                .in_flight_requests
+                .store(self.in_flights.len(), Ordering::Relaxed);
+            let report = Report::new(ChannelClosed::Request)
+                .attach_printable(format!("drop the request {request_id}"));
+            tracing::warn!("{report:?}");
+        }
     }
 
     pub(super) async fn recv_response(&mut self) -> Option<ResponseEnvelope> {
@@ -116,8 +200,14 @@ impl TransportController {
                     };
 
                     let t = TransportResult {
+                        request_id: res.request_id,
                         _request: request,
                         response: r,
+                        error_report: res
+                            .result
+                            .as_ref()
+                            .err()
+                            .map(|report| format!("{report:?}")),
                         request_send: requested_at,
                         response_received: now,
                     };
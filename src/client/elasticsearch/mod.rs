@@ -1,25 +1,66 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
 use elasticsearch::{
     auth::Credentials,
     cat::{CatAliasesParts, CatIndicesParts},
     cluster::ClusterHealthParts,
-    http::transport::Transport,
-    indices::IndicesGetParts,
-    params::{Bytes, ExpandWildcards, Level},
+    http::{
+        headers::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE},
+        response::Response,
+        transport::{CloudConnectionPool, TransportBuilder},
+        Method,
+    },
+    ilm::IlmGetLifecycleParts,
+    indices::{
+        IndicesGetIndexTemplateParts, IndicesGetMappingParts, IndicesGetParts,
+        IndicesGetSettingsParts,
+    },
+    params::{Bytes, ExpandWildcards, GroupBy, Level},
+    snapshot::{SnapshotGetParts, SnapshotGetRepositoryParts},
+    SearchParts,
 };
 use error_stack::{IntoReport, ResultExt};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
 use thiserror::Error;
 
-use crate::ElasticsearchConfig;
+use crate::{CatBytesUnit, ElasticsearchConfig, ElasticsearchCredential};
 
 pub(crate) mod response;
 
+/// Columns requested from `_cat/nodes`.
+pub(crate) const CAT_NODES_COLUMNS: &[&str] = &[
+    "name",
+    "ip",
+    "node.role",
+    "master",
+    "heap.percent",
+    "ram.percent",
+    "cpu",
+    "load_1m",
+    "disk.used_percent",
+];
+
+/// Large enough for the cat apis of big clusters.
+const DEFAULT_MAX_RESPONSE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Major versions of elasticsearch the compatible headers can be sent to.
+const SUPPORTED_API_VERSIONS: &[&str] = &["7", "8"];
+
 #[derive(Debug)]
 pub struct ElasticsearchClient {
     name: String,
     inner: elasticsearch::Elasticsearch,
     default_timeout: Duration,
+    ping_timeout: Duration,
+    max_response_bytes: u64,
+    error_body_bytes: usize,
+    sample_docs_size: i64,
+    cat_bytes_unit: CatBytesUnit,
+    include_unloaded_segments: bool,
+    index_pattern: Option<String>,
+    /// Compatible and configured headers sent with every request.
+    request_headers: HeaderMap,
 }
 
 #[derive(Debug, Error)]
@@ -30,19 +71,58 @@ pub(crate) enum ElasticsearchClientError {
     ApiRequest,
     #[error("deserialize response")]
     DeserializeResponse,
+    #[error("response too large")]
+    ResponseTooLarge,
+}
+
+/// Set the headers of the client on the api request builder.
+///
+/// The transport always sets `Accept` and `Content-Type` itself, so they take effect only per request.
+macro_rules! with_headers {
+    ($client:expr, $request:expr) => {
+        $client
+            .request_headers
+            .iter()
+            .fold($request, |request, (name, value)| {
+                request.header(name.clone(), value.clone())
+            })
+    };
 }
 
 impl ElasticsearchClient {
-    pub(crate) fn new(
+    pub(crate) async fn new(
         c: ElasticsearchConfig,
     ) -> error_stack::Result<Self, ElasticsearchClientError> {
+        let mut request_headers = compatible_headers(c.api_version.as_deref())?;
+        // explicitly configured headers win over the compatible ones.
+        request_headers.extend(default_headers(c.default_headers.unwrap_or_default())?);
+
+        let mut headers = HeaderMap::new();
+
+        let bearer_token = bearer_token(&c.credential).await?;
+        if let Some(token) = bearer_token.as_deref() {
+            headers.insert(AUTHORIZATION, bearer_header(token)?);
+        }
+
         let transport = match c.credential.cloud_id {
-            Some(cloud_id) => Transport::cloud(
-                cloud_id.as_str(),
-                Credentials::Basic(c.credential.username, c.credential.password),
-            )
-            .into_report()
-            .change_context(ElasticsearchClientError::BuildClient)?,
+            Some(cloud_id) => {
+                let pool = CloudConnectionPool::new(cloud_id.as_str())
+                    .into_report()
+                    .change_context(ElasticsearchClientError::BuildClient)?;
+                let builder = TransportBuilder::new(pool);
+                let builder = match bearer_token {
+                    Some(_) => builder,
+                    None => builder.auth(Credentials::Basic(
+                        c.credential.username,
+                        c.credential.password,
+                    )),
+                };
+                builder
+                    .headers(headers)
+                    .build()
+                    .into_report()
+                    .change_context(ElasticsearchClientError::BuildClient)?
+            }
             None => {
                 return Err(error_stack::report!(ElasticsearchClientError::BuildClient))
                     .attach_printable("currently only cloud id credential supported")
@@ -53,6 +133,14 @@ impl ElasticsearchClient {
             name: c.name,
             inner: elasticsearch::Elasticsearch::new(transport),
             default_timeout: Duration::from_secs(20),
+            ping_timeout: Duration::from_secs(c.ping_timeout_secs.unwrap_or(5)),
+            max_response_bytes: c.max_response_bytes.unwrap_or(DEFAULT_MAX_RESPONSE_BYTES),
+            error_body_bytes: c.error_body_bytes.unwrap_or(0),
+            sample_docs_size: c.sample_docs_size.map(i64::from).unwrap_or(5),
+            cat_bytes_unit: c.cat_bytes_unit.unwrap_or_default(),
+            include_unloaded_segments: c.include_unloaded_segments.unwrap_or(false),
+            index_pattern: c.index_pattern,
+            request_headers,
         })
     }
 
@@ -60,55 +148,112 @@ impl ElasticsearchClient {
         self.name.as_str()
     }
 
+    /// Check the cluster is reachable with the credential by `HEAD /`.
+    ///
+    /// Bounded by the ping timeout since there is no body to wait for,
+    /// the client library does not expose the timeout of the connection itself.
+    pub(crate) async fn ping(&self) -> error_stack::Result<(), ElasticsearchClientError> {
+        with_headers!(self, self.inner.ping())
+            .request_timeout(self.ping_timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|_| ())
+    }
+
     // https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-health.html
     pub(crate) async fn get_cluster_health(
         &self,
     ) -> error_stack::Result<response::ClusterHealth, ElasticsearchClientError> {
-        self.inner
-            .cluster()
-            .health(ClusterHealthParts::None)
+        with_headers!(self, self.inner.cluster().health(ClusterHealthParts::None))
             .level(Level::Cluster)
             .local(false)
             .request_timeout(self.default_timeout)
             .send()
             .await
+            .and_then(|response| response.error_for_status_code())
             .into_report()
-            .change_context(ElasticsearchClientError::ApiRequest)?
-            .json::<response::ClusterHealth>()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
             .await
-            .into_report()
-            .change_context(ElasticsearchClientError::DeserializeResponse)
+            .and_then(|body| self.deserialize::<response::ClusterHealth>(&body))
     }
 
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/cat-indices.html
+    ///
+    /// Store sizes are always returned in bytes regardless of the configured unit.
+    /// Indices are scoped by the pattern, or the configured pattern if not given.
     pub(crate) async fn cat_indices(
         &self,
+        pattern: Option<&str>,
     ) -> error_stack::Result<response::CatIndices, ElasticsearchClientError> {
-        self.inner
-            .cat()
-            .indices(CatIndicesParts::None)
-            .bytes(Bytes::B)
+        let pattern = pattern.or(self.index_pattern.as_deref());
+        let parts = match pattern.as_ref() {
+            Some(pattern) => CatIndicesParts::Index(std::slice::from_ref(pattern)),
+            None => CatIndicesParts::None,
+        };
+        let bytes = match self.cat_bytes_unit {
+            CatBytesUnit::B => Bytes::B,
+            CatBytesUnit::Kb => Bytes::Kb,
+            CatBytesUnit::Mb => Bytes::Mb,
+            CatBytesUnit::Gb => Bytes::Gb,
+            CatBytesUnit::Tb => Bytes::Tb,
+            CatBytesUnit::Pb => Bytes::Pb,
+        };
+        let mut indices = with_headers!(self, self.inner.cat().indices(parts))
+            .bytes(bytes)
             .format("json")
-            .include_unloaded_segments(false) // should true ?
+            .include_unloaded_segments(self.include_unloaded_segments)
             .v(false) // ignored in case of json.
             .human(false) // ignored in case of json.
             .request_timeout(self.default_timeout)
             .send()
             .await
+            .and_then(|response| response.error_for_status_code())
             .into_report()
-            .change_context(ElasticsearchClientError::ApiRequest)?
-            .json::<response::CatIndices>()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::CatIndices>(&body))?;
+
+        if self.cat_bytes_unit != CatBytesUnit::B {
+            let unit = self.cat_bytes_unit.bytes();
+            for index in indices.iter_mut() {
+                index.store_size = to_bytes(&index.store_size, unit);
+                index.pri_store_size = index
+                    .pri_store_size
+                    .as_deref()
+                    .map(|size| to_bytes(size, unit));
+            }
+        }
+        Ok(indices)
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/cat-nodes.html
+    pub(crate) async fn cat_nodes(
+        &self,
+    ) -> error_stack::Result<response::CatNodes, ElasticsearchClientError> {
+        with_headers!(self, self.inner.cat().nodes())
+            .format("json")
+            .h(CAT_NODES_COLUMNS)
+            .request_timeout(self.default_timeout)
+            .send()
             .await
+            .and_then(|response| response.error_for_status_code())
             .into_report()
-            .change_context(ElasticsearchClientError::DeserializeResponse)
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::CatNodes>(&body))
     }
 
     /// https://www.elastic.co/guide/en/elasticsearch/reference/current/cat-alias.html
     pub(crate) async fn cat_aliases(
         &self,
     ) -> error_stack::Result<response::CatAliases, ElasticsearchClientError> {
-        self.inner
-            .cat()
-            .aliases(CatAliasesParts::None)
+        with_headers!(self, self.inner.cat().aliases(CatAliasesParts::None))
             .format("json")
             .local(false)
             .v(true)
@@ -116,14 +261,323 @@ impl ElasticsearchClient {
             .request_timeout(self.default_timeout)
             .send()
             .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::CatAliases>(&body))
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-template.html
+    pub(crate) async fn get_index_templates(
+        &self,
+    ) -> error_stack::Result<response::IndexTemplates, ElasticsearchClientError> {
+        let request = self
+            .inner
+            .indices()
+            .get_index_template(IndicesGetIndexTemplateParts::None);
+        with_headers!(self, request)
+            .flat_settings(false)
+            .local(false)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::IndexTemplates>(&body))
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/ilm-get-lifecycle.html
+    ///
+    /// Returns `None` if the cluster does not provide ILM.
+    pub(crate) async fn get_ilm_policies(
+        &self,
+    ) -> error_stack::Result<Option<response::IlmPolicies>, ElasticsearchClientError> {
+        let request = self.inner.ilm().get_lifecycle(IlmGetLifecycleParts::None);
+        let response = with_headers!(self, request)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)?;
+
+        // clusters without ILM respond "no handler found for uri".
+        if matches!(response.status_code().as_u16(), 400 | 404) {
+            return Ok(None);
+        }
+
+        response
+            .error_for_status_code()
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::IlmPolicies>(&body))
+            .map(Some)
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/get-snapshot-repo-api.html
+    pub(crate) async fn get_repositories(
+        &self,
+    ) -> error_stack::Result<response::SnapshotRepositories, ElasticsearchClientError> {
+        let request = self
+            .inner
+            .snapshot()
+            .get_repository(SnapshotGetRepositoryParts::None);
+        with_headers!(self, request)
+            .local(false)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::SnapshotRepositories>(&body))
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/get-snapshot-api.html
+    pub(crate) async fn get_snapshots(
+        &self,
+        repository: &str,
+    ) -> error_stack::Result<response::Snapshots, ElasticsearchClientError> {
+        let request = self
+            .inner
+            .snapshot()
+            .get(SnapshotGetParts::RepositorySnapshot(repository, &["_all"]));
+        with_headers!(self, request)
+            .ignore_unavailable(true)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::Snapshots>(&body))
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-get-settings.html
+    pub(crate) async fn get_cluster_settings(
+        &self,
+    ) -> error_stack::Result<response::ClusterSettings, ElasticsearchClientError> {
+        with_headers!(self, self.inner.cluster().get_settings())
+            .include_defaults(false)
+            .flat_settings(true)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::ClusterSettings>(&body))
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/tasks.html
+    pub(crate) async fn get_tasks(
+        &self,
+    ) -> error_stack::Result<response::Tasks, ElasticsearchClientError> {
+        with_headers!(self, self.inner.tasks().list())
+            .detailed(true)
+            .group_by(GroupBy::Parents)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
             .into_report()
-            .change_context(ElasticsearchClientError::ApiRequest)?
-            .json::<response::CatAliases>()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::Tasks>(&body))
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-allocation-explain.html
+    ///
+    /// Explain an unassigned shard, returns `None` if all shards are assigned.
+    pub(crate) async fn allocation_explain(
+        &self,
+    ) -> error_stack::Result<Option<response::AllocationExplain>, ElasticsearchClientError> {
+        let response = with_headers!(self, self.inner.cluster().allocation_explain())
+            // empty body lets elasticsearch pick an unassigned shard.
+            .body(serde_json::json!({}))
+            .include_disk_info(false)
+            .include_yes_decisions(false)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)?;
+
+        // there is no shard to pick if all shards are assigned.
+        if response.status_code().as_u16() == 400 {
+            return Ok(None);
+        }
+
+        response
+            .error_for_status_code()
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::AllocationExplain>(&body))
+            .map(Some)
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
+    pub(crate) async fn sample_docs(
+        &self,
+        index: &str,
+        from: i64,
+    ) -> error_stack::Result<response::Search, ElasticsearchClientError> {
+        with_headers!(self, self.inner.search(SearchParts::Index(&[index])))
+            .from(from)
+            .size(self.sample_docs_size)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::Search>(&body))
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-settings.html
+    pub(crate) async fn get_index_settings(
+        &self,
+        index: &str,
+    ) -> error_stack::Result<response::IndexSettings, ElasticsearchClientError> {
+        let request = self
+            .inner
+            .indices()
+            .get_settings(IndicesGetSettingsParts::Index(&[index]));
+        with_headers!(self, request)
+            .request_timeout(self.default_timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::GetIndexSettings>(&body))?
+            .into_values()
+            .next()
+            .map(|entry| entry.settings.index)
+            .ok_or_else(|| error_stack::report!(ElasticsearchClientError::DeserializeResponse))
+            .attach_printable_lazy(|| format!("settings of {index} not found"))
+    }
+
+    /// https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-mapping.html
+    pub(crate) async fn get_index_mappings(
+        &self,
+        index: &str,
+    ) -> error_stack::Result<response::IndexMappings, ElasticsearchClientError> {
+        let request = self
+            .inner
+            .indices()
+            .get_mapping(IndicesGetMappingParts::Index(&[index]));
+        with_headers!(self, request)
+            .request_timeout(self.default_timeout)
+            .send()
             .await
+            .and_then(|response| response.error_for_status_code())
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)
+            .map(|response| self.read_body(response))?
+            .await
+            .and_then(|body| self.deserialize::<response::GetMappings>(&body))?
+            .into_values()
+            .next()
+            .map(|entry| entry.mappings)
+            .ok_or_else(|| error_stack::report!(ElasticsearchClientError::DeserializeResponse))
+            .attach_printable_lazy(|| format!("mappings of {index} not found"))
+    }
+
+    /// `GET` the path as typed, like `_cat/health?v`, to explore the cluster.
+    ///
+    /// The body is returned whatever the status is since it explains the error.
+    pub(crate) async fn raw_get(
+        &self,
+        path: &str,
+    ) -> error_stack::Result<response::RawResponse, ElasticsearchClientError> {
+        let response = self
+            .inner
+            .send(
+                Method::Get,
+                format!("/{}", path.trim_start_matches('/')).as_str(),
+                self.request_headers.clone(),
+                None::<&()>,
+                None::<&'static str>,
+                Some(self.default_timeout),
+            )
+            .await
+            .into_report()
+            .change_context(ElasticsearchClientError::ApiRequest)?;
+
+        let status = response.status_code().as_u16();
+        let body = self.read_body(response).await?;
+        Ok(response::RawResponse { status, body })
+    }
+
+    /// Read the body up to `max_response_bytes`, so that an endpoint other than elasticsearch
+    /// fails fast instead of exhausting the memory.
+    async fn read_body(
+        &self,
+        response: Response,
+    ) -> error_stack::Result<String, ElasticsearchClientError> {
+        let max = self.max_response_bytes;
+        let too_large = |size: u64| {
+            error_stack::report!(ElasticsearchClientError::ResponseTooLarge)
+                .attach_printable(format!("{size} bytes exceeds max_response_bytes {max}"))
+        };
+        if let Some(length) = response.content_length().filter(|length| *length > max) {
+            return Err(too_large(length));
+        }
+        // chunked responses do not tell the length in advance, stop at the chunk exceeding it.
+        let stream = response.bytes_stream();
+        futures::pin_mut!(stream);
+        let mut body = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk
+                .into_report()
+                .change_context(ElasticsearchClientError::DeserializeResponse)?;
+            let size = (body.len() + chunk.len()) as u64;
+            if size > max {
+                return Err(too_large(size));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        String::from_utf8(body)
             .into_report()
             .change_context(ElasticsearchClientError::DeserializeResponse)
     }
 
+    /// Deserialize the response body keeping the serde error, which tells the failed field and position.
+    /// The head of the body is attached if configured, like the html error page of a proxy.
+    fn deserialize<T>(&self, body: &str) -> error_stack::Result<T, ElasticsearchClientError>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_str(body).map_err(|err| {
+            let report = error_stack::report!(ElasticsearchClientError::DeserializeResponse)
+                .attach_printable(err.to_string());
+            match self.error_body_bytes {
+                0 => report,
+                max => report.attach_printable(format!("body: {}", body_head(body, max))),
+            }
+        })
+    }
+
     /// https://www.elastic.co/guide/en/elasticsearch/reference/8.5/indices-get-index.html
     pub async fn get_all_indices(&self) {
         //   features()の引数に複数のFeatureを渡せないので、default値のaliases,mappings,settingsを暗黙的に利用する
@@ -152,6 +606,140 @@ impl ElasticsearchClient {
     }
 }
 
+/// Short reason of the failure, like "timeout", to summarize failures across clusters.
+pub(crate) fn failure_reason(report: &error_stack::Report<ElasticsearchClientError>) -> String {
+    match report.downcast_ref::<elasticsearch::Error>() {
+        Some(err) if err.is_timeout() => "timeout".to_owned(),
+        Some(err) => match err.status_code().map(|status| status.as_u16()) {
+            Some(401 | 403) => "auth".to_owned(),
+            Some(status) => format!("status {status}"),
+            None => "connection".to_owned(),
+        },
+        None => report.current_context().to_string(),
+    }
+}
+
+/// Head of the body up to `max` bytes, cut at a char boundary.
+fn body_head(body: &str, max: usize) -> String {
+    if body.len() <= max {
+        return body.to_owned();
+    }
+    let end = (0..=max)
+        .rev()
+        .find(|i| body.is_char_boundary(*i))
+        .unwrap_or(0);
+    format!("{}...", &body[..end])
+}
+
+/// Media type of the requests and responses compatible with the major version.
+pub(crate) fn compatible_media_type(api_version: &str) -> String {
+    format!("application/vnd.elasticsearch+json; compatible-with={api_version}")
+}
+
+/// `Accept` and `Content-Type` of the api version, nothing to keep the library default.
+fn compatible_headers(
+    api_version: Option<&str>,
+) -> error_stack::Result<HeaderMap, ElasticsearchClientError> {
+    let mut map = HeaderMap::new();
+    let api_version = match api_version {
+        Some(api_version) => api_version,
+        None => return Ok(map),
+    };
+    if !SUPPORTED_API_VERSIONS.contains(&api_version) {
+        return Err(error_stack::report!(ElasticsearchClientError::BuildClient)).attach_printable(
+            format!(
+                "unsupported api version {api_version}, expected one of {}",
+                SUPPORTED_API_VERSIONS.join(", ")
+            ),
+        );
+    }
+
+    let media_type = HeaderValue::from_str(compatible_media_type(api_version).as_str())
+        .into_report()
+        .change_context(ElasticsearchClientError::BuildClient)?;
+    map.insert(ACCEPT, media_type.clone());
+    map.insert(CONTENT_TYPE, media_type);
+    Ok(map)
+}
+
+/// Validate the configured headers, invalid names or values fail to build the client.
+fn default_headers(
+    headers: BTreeMap<String, String>,
+) -> error_stack::Result<HeaderMap, ElasticsearchClientError> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .into_report()
+            .change_context(ElasticsearchClientError::BuildClient)
+            .attach_printable_lazy(|| format!("invalid header name {name}"))?;
+        let header_value = HeaderValue::from_str(value.as_str())
+            .into_report()
+            .change_context(ElasticsearchClientError::BuildClient)
+            .attach_printable_lazy(|| format!("invalid value of header {name}"))?;
+        map.insert(header_name, header_value);
+    }
+    Ok(map)
+}
+
+/// Token to send as `Authorization: Bearer`, `None` to use the basic auth.
+///
+/// token command > bearer token > basic auth.
+async fn bearer_token(
+    credential: &ElasticsearchCredential,
+) -> error_stack::Result<Option<String>, ElasticsearchClientError> {
+    match credential.token_command.as_deref() {
+        Some(command) => run_token_command(command).await.map(Some),
+        None => Ok(credential.bearer_token.clone()),
+    }
+}
+
+/// Run the command with the shell and take the trimmed stdout as the token.
+async fn run_token_command(command: &str) -> error_stack::Result<String, ElasticsearchClientError> {
+    let output = tokio::process::Command::new("sh")
+        .args(["-c", command])
+        .output()
+        .await
+        .into_report()
+        .change_context(ElasticsearchClientError::BuildClient)
+        .attach_printable_lazy(|| format!("run token command {command}"))?;
+
+    if !output.status.success() {
+        return Err(error_stack::report!(ElasticsearchClientError::BuildClient))
+            .attach_printable(format!(
+                "token command {command} exited with {}",
+                output.status
+            ))
+            .attach_printable(String::from_utf8_lossy(&output.stderr).trim().to_owned());
+    }
+
+    let token = String::from_utf8(output.stdout)
+        .into_report()
+        .change_context(ElasticsearchClientError::BuildClient)
+        .attach_printable("token command printed non utf8 output")?;
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(error_stack::report!(ElasticsearchClientError::BuildClient))
+            .attach_printable(format!("token command {command} printed no token"));
+    }
+    Ok(token.to_owned())
+}
+
+fn bearer_header(token: &str) -> error_stack::Result<HeaderValue, ElasticsearchClientError> {
+    let mut value = HeaderValue::from_str(format!("Bearer {token}").as_str())
+        .into_report()
+        .change_context(ElasticsearchClientError::BuildClient)
+        .attach_printable("invalid bearer token")?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+/// Convert the size in the unit to bytes, closed indices have no size and are kept as is.
+fn to_bytes(size: &str, unit: u64) -> String {
+    size.parse::<u64>()
+        .map(|n| n.saturating_mul(unit).to_string())
+        .unwrap_or_else(|_| size.to_owned())
+}
+
 // Elasticsearch apiの時間の指定方法。
 // https://www.elastic.co/guide/en/elasticsearch/reference/8.5/api-conventions.html#time-units
 trait TimeUnit {
@@ -163,3 +751,337 @@ impl TimeUnit for std::time::Duration {
         format!("{}s", self.as_secs())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use elasticsearch::http::transport::Transport;
+
+    use super::*;
+
+    /// Client of the server on the url, built without a config to skip the cloud id.
+    fn local_client(url: &str, request_headers: HeaderMap) -> ElasticsearchClient {
+        ElasticsearchClient {
+            name: "local".to_owned(),
+            inner: elasticsearch::Elasticsearch::new(Transport::single_node(url).unwrap()),
+            default_timeout: Duration::from_secs(5),
+            ping_timeout: Duration::from_secs(5),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            error_body_bytes: 0,
+            sample_docs_size: 5,
+            cat_bytes_unit: CatBytesUnit::B,
+            include_unloaded_segments: false,
+            index_pattern: None,
+            request_headers,
+        }
+    }
+
+    /// Serve one request with the response, the received request head is returned.
+    fn serve_once(response: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                head.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8(head).unwrap()
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn to_bytes_multiplies_by_unit() {
+        assert_eq!(to_bytes("5", 1024), "5120");
+        assert_eq!(to_bytes("0", 1024 * 1024), "0");
+        assert_eq!(to_bytes("5", 1), "5");
+    }
+
+    #[test]
+    fn to_bytes_keeps_missing_size() {
+        // closed indices have no size.
+        assert_eq!(to_bytes("", 1024), "");
+        assert_eq!(to_bytes("-", 1024), "-");
+    }
+
+    #[test]
+    fn to_bytes_saturates() {
+        assert_eq!(to_bytes(&u64::MAX.to_string(), 1024), u64::MAX.to_string());
+    }
+
+    #[test]
+    fn default_headers_round_trip() {
+        let headers = default_headers(BTreeMap::from([
+            ("X-Tenant".to_owned(), "tenant-a".to_owned()),
+            ("x-request-source".to_owned(), "console".to_owned()),
+        ]))
+        .unwrap();
+
+        assert_eq!(headers.len(), 2);
+        // header names are case insensitive.
+        assert_eq!(headers.get("x-tenant").unwrap(), "tenant-a");
+        assert_eq!(headers.get("X-Request-Source").unwrap(), "console");
+    }
+
+    #[test]
+    fn default_headers_reject_invalid_name() {
+        let report =
+            default_headers(BTreeMap::from([("X Tenant".to_owned(), "a".to_owned())])).unwrap_err();
+        assert!(matches!(
+            report.current_context(),
+            ElasticsearchClientError::BuildClient
+        ));
+    }
+
+    #[test]
+    fn default_headers_reject_invalid_value() {
+        let report = default_headers(BTreeMap::from([(
+            "X-Tenant".to_owned(),
+            "tenant\na".to_owned(),
+        )]))
+        .unwrap_err();
+        assert!(matches!(
+            report.current_context(),
+            ElasticsearchClientError::BuildClient
+        ));
+    }
+
+    #[test]
+    fn compatible_headers_of_version() {
+        let headers = compatible_headers(Some("7")).unwrap();
+        let media_type = "application/vnd.elasticsearch+json; compatible-with=7";
+        assert_eq!(compatible_media_type("7"), media_type);
+        assert_eq!(headers.get(ACCEPT).unwrap(), media_type);
+        assert_eq!(headers.get(CONTENT_TYPE).unwrap(), media_type);
+
+        assert!(compatible_headers(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn compatible_headers_reject_unsupported_version() {
+        let report = compatible_headers(Some("6")).unwrap_err();
+        assert!(matches!(
+            report.current_context(),
+            ElasticsearchClientError::BuildClient
+        ));
+    }
+
+    #[tokio::test]
+    async fn request_headers_reach_the_wire() {
+        let (url, server) = serve_once("HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n[]");
+        let mut headers = compatible_headers(Some("7")).unwrap();
+        headers.extend(
+            default_headers(BTreeMap::from([(
+                "X-Tenant".to_owned(),
+                "tenant-a".to_owned(),
+            )]))
+            .unwrap(),
+        );
+
+        local_client(&url, headers).cat_aliases().await.unwrap();
+
+        // the transport sets its own accept and content type unless given per request.
+        let head = server.join().unwrap().to_lowercase();
+        let media_type = "application/vnd.elasticsearch+json; compatible-with=7";
+        assert!(
+            head.contains(&format!("\r\naccept: {media_type}\r\n")),
+            "{head}"
+        );
+        assert!(
+            head.contains(&format!("\r\ncontent-type: {media_type}\r\n")),
+            "{head}"
+        );
+        assert!(head.contains("\r\nx-tenant: tenant-a\r\n"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn ping_timeout_applied() {
+        let config = |ping_timeout_secs| {
+            ElasticsearchConfig::builder()
+                .name("cluster-a".to_owned())
+                .endpoint("https://localhost:9200".parse().unwrap())
+                .credential(
+                    ElasticsearchCredential::builder()
+                        .cloud_id(Some("cluster-a:bG9jYWxob3N0JGVzJGtpYmFuYQ==".to_owned()))
+                        .build(),
+                )
+                .ping_timeout_secs(ping_timeout_secs)
+                .build()
+        };
+
+        let client = ElasticsearchClient::new(config(Some(2))).await.unwrap();
+        assert_eq!(client.ping_timeout, Duration::from_secs(2));
+        // requests keep the longer timeout to load large responses.
+        assert_eq!(client.default_timeout, Duration::from_secs(20));
+
+        let client = ElasticsearchClient::new(config(None)).await.unwrap();
+        assert_eq!(client.ping_timeout, Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn error_status_fails_the_request() {
+        let (url, server) =
+            serve_once("HTTP/1.1 503 Service Unavailable\r\ncontent-length: 2\r\n\r\n[]");
+
+        let report = local_client(&url, HeaderMap::new())
+            .cat_aliases()
+            .await
+            .unwrap_err();
+        server.join().unwrap();
+
+        // the error body must not be taken for an empty list.
+        assert!(matches!(
+            report.current_context(),
+            ElasticsearchClientError::ApiRequest
+        ));
+    }
+
+    #[test]
+    fn deserialize_error_tells_the_field() {
+        let mut client = local_client("http://127.0.0.1:9200", HeaderMap::new());
+        let body = r#"[{"index":"logs-app","health":"green"}]"#;
+
+        let report = client
+            .deserialize::<response::CatIndices>(body)
+            .unwrap_err();
+        let reason = crate::app::error_reason(&report);
+        assert!(
+            reason.starts_with("deserialize response: missing field `docs.count` at line 1"),
+            "{reason}"
+        );
+
+        client.error_body_bytes = 10;
+        let report = client
+            .deserialize::<response::CatIndices>(body)
+            .unwrap_err();
+        let reason = crate::app::error_reason(&report);
+        assert!(reason.contains(r#"body: [{"index":..."#), "{reason}");
+    }
+
+    #[tokio::test]
+    async fn oversized_chunked_body_fails_fast() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        // the body never ends, the client must give up without waiting for it.
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf);
+            let head = "HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n";
+            let _ = stream.write_all(head.as_bytes());
+            let chunk = format!("400\r\n{}\r\n", "a".repeat(0x400));
+            while stream.write_all(chunk.as_bytes()).is_ok() {
+                thread::sleep(Duration::from_millis(10));
+            }
+        });
+        let mut client = local_client(&url, HeaderMap::new());
+        client.max_response_bytes = 4096;
+
+        let report = tokio::time::timeout(Duration::from_secs(5), client.cat_aliases())
+            .await
+            .expect("waited for the whole body")
+            .unwrap_err();
+        assert!(matches!(
+            report.current_context(),
+            ElasticsearchClientError::ResponseTooLarge
+        ));
+    }
+
+    #[tokio::test]
+    async fn oversized_body_with_length_fails() {
+        let (url, server) = serve_once("HTTP/1.1 200 OK\r\ncontent-length: 10\r\n\r\n[\"aaaaaa\"]");
+        let mut client = local_client(&url, HeaderMap::new());
+        client.max_response_bytes = 8;
+
+        let report = client.raw_get("_cat/aliases").await.unwrap_err();
+        server.join().unwrap();
+        assert!(matches!(
+            report.current_context(),
+            ElasticsearchClientError::ResponseTooLarge
+        ));
+    }
+
+    fn credential(
+        bearer_token: Option<&str>,
+        token_command: Option<&str>,
+    ) -> ElasticsearchCredential {
+        ElasticsearchCredential::builder()
+            .username("elastic".to_owned())
+            .cloud_id(None)
+            .bearer_token(bearer_token.map(str::to_owned))
+            .token_command(token_command.map(str::to_owned))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn token_command_takes_precedence() {
+        let token = bearer_token(&credential(Some("static"), Some("echo ' fresh '")))
+            .await
+            .unwrap();
+        assert_eq!(token.as_deref(), Some("fresh"));
+    }
+
+    #[tokio::test]
+    async fn bearer_token_without_command() {
+        let token = bearer_token(&credential(Some("static"), None))
+            .await
+            .unwrap();
+        assert_eq!(token.as_deref(), Some("static"));
+
+        // falls back to the basic auth.
+        assert_eq!(bearer_token(&credential(None, None)).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn token_command_failures() {
+        for command in ["exit 1", "true", "printf '\\377'"] {
+            let report = bearer_token(&credential(None, Some(command)))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(
+                    report.current_context(),
+                    ElasticsearchClientError::BuildClient
+                ),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn bearer_header_is_sensitive() {
+        let value = bearer_header("abc").unwrap();
+        assert_eq!(value, "Bearer abc");
+        assert!(value.is_sensitive());
+
+        assert!(bearer_header("a\nb").is_err());
+    }
+
+    #[test]
+    fn body_head_keeps_short_body() {
+        assert_eq!(body_head("<html>", 6), "<html>");
+        assert_eq!(body_head("", 6), "");
+    }
+
+    #[test]
+    fn body_head_cuts_long_body() {
+        let body = "<html>503 Service Unavailable</html>";
+        assert_eq!(body_head(body, 29), "<html>503 Service Unavailable...");
+    }
+
+    #[test]
+    fn body_head_cuts_at_char_boundary() {
+        // "é" takes 2 bytes, cutting at 2 would split it.
+        assert_eq!(body_head("aéb", 2), "a...");
+        assert_eq!(body_head("aéb", 3), "aé...");
+    }
+}
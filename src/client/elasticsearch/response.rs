@@ -1,49 +1,116 @@
-use serde::Deserialize;
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-health.html#cluster-health-api-response-body
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ClusterHealth {
+    #[serde(deserialize_with = "lenient_number")]
     pub active_primary_shards: i64,
+    #[serde(deserialize_with = "lenient_number")]
     pub active_shards: i64,
+    #[serde(deserialize_with = "lenient_number")]
     pub active_shards_percent_as_number: f64,
     pub cluster_name: String,
+    #[serde(deserialize_with = "lenient_number")]
     pub delayed_unassigned_shards: i64,
+    #[serde(deserialize_with = "lenient_number")]
     pub initializing_shards: i64,
+    #[serde(deserialize_with = "lenient_number")]
     pub number_of_data_nodes: i64,
+    #[serde(deserialize_with = "lenient_number")]
     pub number_of_in_flight_fetch: i64,
+    #[serde(deserialize_with = "lenient_number")]
     pub number_of_nodes: i64,
+    #[serde(deserialize_with = "lenient_number")]
     pub number_of_pending_tasks: i64,
+    #[serde(deserialize_with = "lenient_number")]
     pub relocating_shards: i64,
     pub status: String,
+    #[serde(deserialize_with = "lenient_number")]
     pub task_max_waiting_in_queue_millis: i64,
     pub timed_out: bool,
+    #[serde(deserialize_with = "lenient_number")]
     pub unassigned_shards: i64,
 }
 
+/// Number as json number or string, some versions and proxies stringify them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString<T> {
+    Number(T),
+    String(String),
+}
+
+fn lenient_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: Display,
+{
+    match NumberOrString::<T>::deserialize(deserializer)? {
+        NumberOrString::Number(n) => Ok(n),
+        NumberOrString::String(s) => s.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Null as an empty string, which is shown as "-" like a missing value.
+fn null_as_empty<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).map(Option::unwrap_or_default)
+}
+
 /// https://www.elastic.co/guide/en/elasticsearch/reference/current/cat-indices.html
 pub type CatIndices = Vec<CatIndex>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CatIndex {
-    #[serde(rename = "docs.count")]
+    /// Null on closed indices, as well as `docs_deleted` and `store_size`.
+    #[serde(rename = "docs.count", deserialize_with = "null_as_empty")]
     pub docs_count: String,
-    #[serde(rename = "docs.deleted")]
+    #[serde(rename = "docs.deleted", deserialize_with = "null_as_empty")]
     pub docs_deleted: String,
     pub health: String,
     pub index: String,
     pub pri: String,
+    /// Missing on closed indices.
     #[serde(rename = "pri.store.size")]
-    pub pri_store_size: String,
+    pub pri_store_size: Option<String>,
     pub rep: String,
     pub status: String,
-    #[serde(rename = "store.size")]
+    #[serde(rename = "store.size", deserialize_with = "null_as_empty")]
     pub store_size: String,
-    pub uuid: String,
+    /// Omitted by some versions, a missing field should not fail the whole table.
+    pub uuid: Option<String>,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/cat-nodes.html
+pub type CatNodes = Vec<CatNode>;
+
+/// Metrics may be null while the node is starting.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CatNode {
+    pub name: String,
+    pub ip: String,
+    #[serde(rename = "node.role")]
+    pub node_role: String,
+    /// "*" for the elected master.
+    pub master: String,
+    #[serde(rename = "heap.percent")]
+    pub heap_percent: Option<String>,
+    #[serde(rename = "ram.percent")]
+    pub ram_percent: Option<String>,
+    pub cpu: Option<String>,
+    pub load_1m: Option<String>,
+    #[serde(rename = "disk.used_percent")]
+    pub disk_used_percent: Option<String>,
 }
 
 pub type CatAliases = Vec<CatAlias>;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CatAlias {
     pub alias: String,
     pub filter: String,
@@ -55,3 +122,294 @@ pub struct CatAlias {
     #[serde(rename = "routing.search")]
     pub routing_search: String,
 }
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-template.html
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IndexTemplates {
+    pub index_templates: Vec<IndexTemplateEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IndexTemplateEntry {
+    pub name: String,
+    pub index_template: IndexTemplate,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IndexTemplate {
+    pub index_patterns: Vec<String>,
+    pub priority: Option<i64>,
+    #[serde(default)]
+    pub composed_of: Vec<String>,
+    /// settings, mappings and aliases applied to matching indices.
+    pub template: Option<serde_json::Value>,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/ilm-get-lifecycle.html#ilm-get-lifecycle-example
+pub type IlmPolicies = BTreeMap<String, IlmPolicy>;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IlmPolicy {
+    pub version: Option<i64>,
+    pub modified_date: Option<String>,
+    pub policy: IlmPolicyBody,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IlmPolicyBody {
+    #[serde(default)]
+    pub phases: BTreeMap<String, IlmPhase>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IlmPhase {
+    pub min_age: Option<String>,
+    #[serde(default)]
+    pub actions: BTreeMap<String, serde_json::Value>,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/get-snapshot-repo-api.html#get-snapshot-repo-api-response-body
+pub type SnapshotRepositories = BTreeMap<String, SnapshotRepository>;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SnapshotRepository {
+    #[serde(rename = "type")]
+    pub repository_type: String,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/get-snapshot-api.html#get-snapshot-api-response-body
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Snapshots {
+    pub snapshots: Vec<Snapshot>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Snapshot {
+    pub snapshot: String,
+    /// "IN_PROGRESS" / "SUCCESS" / "FAILED" / "PARTIAL" / "INCOMPATIBLE"
+    pub state: String,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    #[serde(default)]
+    pub indices: Vec<String>,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-get-settings.html
+/// Requested with `flat_settings`, so keys are dotted like `cluster.routing.allocation.enable`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClusterSettings {
+    #[serde(default)]
+    pub persistent: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub transient: BTreeMap<String, serde_json::Value>,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-settings.html
+/// Keyed by the index name.
+pub type GetIndexSettings = BTreeMap<String, IndexSettingsEntry>;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IndexSettingsEntry {
+    pub settings: Settings,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Settings {
+    pub index: IndexSettings,
+}
+
+/// Numbers are strings in the settings.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IndexSettings {
+    pub number_of_shards: String,
+    pub number_of_replicas: String,
+    pub uuid: String,
+    pub provided_name: Option<String>,
+    /// Epoch milliseconds.
+    pub creation_date: String,
+    pub analysis: Option<Analysis>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Analysis {
+    /// Custom analyzers by name.
+    pub analyzer: Option<BTreeMap<String, serde_json::Value>>,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-mapping.html
+/// Keyed by the index name.
+pub type GetMappings = BTreeMap<String, IndexMappingsEntry>;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IndexMappingsEntry {
+    pub mappings: IndexMappings,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IndexMappings {
+    /// Fields by name, object fields nest their own `properties`.
+    #[serde(default)]
+    pub properties: BTreeMap<String, serde_json::Value>,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-allocation-explain.html#cluster-allocation-explain-api-response-body
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AllocationExplain {
+    pub index: String,
+    pub shard: i64,
+    pub primary: bool,
+    pub current_state: String,
+    pub unassigned_info: Option<UnassignedInfo>,
+    /// Named `allocate_explanation` before 8.x.
+    #[serde(alias = "allocate_explanation")]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub node_allocation_decisions: Vec<NodeAllocationDecision>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UnassignedInfo {
+    /// "INDEX_CREATED", "NODE_LEFT" and so on.
+    pub reason: String,
+    pub at: String,
+    pub last_allocation_status: Option<String>,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NodeAllocationDecision {
+    pub node_name: String,
+    /// "yes", "no", "throttled" and so on.
+    pub node_decision: String,
+    /// Only the deciders which did not say yes.
+    #[serde(default)]
+    pub deciders: Vec<AllocationDecider>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AllocationDecider {
+    pub decider: String,
+    pub decision: String,
+    pub explanation: String,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/tasks.html
+/// Requested with `group_by=parents`, so only the parent tasks are keyed by the task id.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Tasks {
+    #[serde(default)]
+    pub tasks: BTreeMap<String, Task>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Task {
+    pub node: String,
+    pub id: u64,
+    pub action: String,
+    /// Only with `detailed`, empty for some actions.
+    pub description: Option<String>,
+    pub running_time_in_nanos: u64,
+    pub cancellable: bool,
+    #[serde(default)]
+    pub children: Vec<Task>,
+}
+
+/// https://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html#search-api-response-body
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Search {
+    pub hits: SearchHits,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchHits {
+    pub total: Option<SearchTotal>,
+    pub hits: Vec<SearchHit>,
+}
+
+/// Counted up to 10,000 by default, "gte" if there are more.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchTotal {
+    pub value: u64,
+    /// "eq" / "gte"
+    pub relation: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchHit {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_source")]
+    pub source: Option<serde_json::Value>,
+}
+
+/// Response of an arbitrary `GET`, kept as text since not every api returns json.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RawResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Health of a green cluster with the active shards given as the json value.
+    fn cluster_health(active_shards: &str) -> String {
+        format!(
+            r#"{{
+                "cluster_name": "cluster-a",
+                "status": "green",
+                "timed_out": false,
+                "number_of_nodes": 3,
+                "number_of_data_nodes": 3,
+                "active_primary_shards": 5,
+                "active_shards": {active_shards},
+                "relocating_shards": 0,
+                "initializing_shards": 0,
+                "unassigned_shards": 0,
+                "delayed_unassigned_shards": 0,
+                "number_of_pending_tasks": 0,
+                "number_of_in_flight_fetch": 0,
+                "task_max_waiting_in_queue_millis": 0,
+                "active_shards_percent_as_number": "100.0"
+            }}"#
+        )
+    }
+
+    #[test]
+    fn cluster_health_with_number() {
+        let health: ClusterHealth = serde_json::from_str(&cluster_health("5")).unwrap();
+        assert_eq!(health.active_shards, 5);
+        assert_eq!(health.active_shards_percent_as_number, 100.0);
+    }
+
+    #[test]
+    fn cluster_health_with_stringified_number() {
+        let health: ClusterHealth = serde_json::from_str(&cluster_health(r#""5""#)).unwrap();
+        assert_eq!(health.active_shards, 5);
+    }
+
+    #[test]
+    fn cluster_health_rejects_non_number() {
+        assert!(serde_json::from_str::<ClusterHealth>(&cluster_health(r#""five""#)).is_err());
+    }
+
+    #[test]
+    fn cat_indices_with_closed_index() {
+        let body = r#"[
+            {"health":"green","status":"open","index":"logs-app","uuid":"u1","pri":"1","rep":"1","docs.count":"10","docs.deleted":"0","store.size":"2048","pri.store.size":"1024"},
+            {"health":"red","status":"close","index":"logs-old","uuid":null,"pri":"1","rep":"1","docs.count":null,"docs.deleted":null,"store.size":null,"pri.store.size":null}
+        ]"#;
+        let indices: CatIndices = serde_json::from_str(body).unwrap();
+
+        assert_eq!(indices.len(), 2);
+        assert_eq!(indices[0].uuid.as_deref(), Some("u1"));
+        assert_eq!(indices[0].pri_store_size.as_deref(), Some("1024"));
+        assert_eq!(indices[1].index, "logs-old");
+        assert_eq!(indices[1].uuid, None);
+        assert_eq!(indices[1].pri_store_size, None);
+        assert_eq!(indices[1].docs_count, "");
+        assert_eq!(indices[1].docs_deleted, "");
+        assert_eq!(indices[1].store_size, "");
+    }
+}
@@ -1,23 +1,758 @@
-use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use error_stack::{IntoReport, ResultExt};
+use serde::{
+    de::{self, DeserializeOwned, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use thiserror::Error;
 use typed_builder::TypedBuilder;
 use url::Url;
 
+/// Env to render in the main screen regardless of the config.
+const NO_ALTERNATE_SCREEN_ENV: &str = "NO_ALTERNATE_SCREEN";
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("read config file")]
+    Read,
+    #[error("parse config file")]
+    Parse,
+    #[error("write state file")]
+    Write,
+}
+
 #[derive(Clone, Debug, Deserialize, TypedBuilder)]
 pub struct Config {
+    /// File the config is loaded from, to reload it.
+    #[serde(skip)]
+    #[builder(default)]
+    pub(crate) source: Option<PathBuf>,
     pub(crate) elasticsearch: Option<Vec<ElasticsearchConfig>>,
+    /// Name of the cluster selected on startup.
+    #[builder(default)]
+    pub(crate) default_cluster: Option<String>,
+    /// Elasticsearch resource selected on startup. "cluster", "index", "alias" and so on.
+    #[builder(default)]
+    pub(crate) default_resource: Option<String>,
+    /// Filter of the index table on startup, the name of `index_filters` or an index name pattern.
+    #[builder(default)]
+    pub(crate) default_index_filter: Option<String>,
+    /// File to append json lines log to.
+    #[builder(default)]
+    pub(crate) log_file: Option<PathBuf>,
+    #[builder(default)]
+    pub(crate) theme: Option<ThemeConfig>,
+    #[builder(default)]
+    pub(crate) preflight: Option<PreflightConfig>,
+    /// Named index name patterns to cycle through in the index table. `*` matches any characters.
+    #[builder(default)]
+    pub(crate) index_filters: Option<BTreeMap<String, String>>,
+    /// Initial order of the index table. default "name".
+    #[builder(default)]
+    pub(crate) index_default_sort: Option<IndexSort>,
+    /// Show dot-prefixed system indices on startup.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) show_hidden_indices: bool,
+    /// Units of the humanized sizes, "binary" (KiB, MiB) or "decimal" (KB, MB). default "binary".
+    #[builder(default)]
+    pub(crate) byte_units: Option<ByteUnits>,
+    /// Show the store sizes "humanized", as "both" humanized and raw bytes or as "raw" bytes.
+    /// default "humanized".
+    #[builder(default)]
+    pub(crate) byte_display: Option<ByteDisplay>,
+    /// Separator of thousands in shard and document counts, "" to show them as is. default ",".
+    #[builder(default)]
+    pub(crate) thousands_separator: Option<String>,
+    /// Columns of the index table in order, like ["Index", "Health", "StoreSize"].
+    /// default all but "Shards".
+    #[builder(default)]
+    pub(crate) index_columns: Option<Vec<IndexColumn>>,
+    /// Show the primary and replica shards of an index in one "pri/rep" column like "3/1".
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) merge_shard_columns: bool,
+    /// Ring the terminal bell when the health of a cluster turns red.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) alert_on_red: bool,
+    /// File to save the last cluster, resource, index filter and sort on quit and restore them on startup.
+    #[builder(default)]
+    pub(crate) state_file: Option<PathBuf>,
+    /// Animate the in-flight spinner, false renders static text for recordings. default true.
+    #[builder(default)]
+    pub(crate) animations: Option<bool>,
+    /// Render in the alternate screen, false renders in the main screen to leave the last view
+    /// in the scrollback on quit. default true.
+    #[builder(default)]
+    pub(crate) alternate_screen: Option<bool>,
+    /// Fetch the health, indices and aliases of the default cluster on startup, not only the
+    /// selected resource.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) prefetch_on_startup: bool,
+    /// Fetch the selected resource again every time a component showing it is focused.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) refresh_on_focus: bool,
+    /// Collapse the indices sharing a name prefix, like daily indices, into one row of the index
+    /// table on startup.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) group_indices: bool,
+    /// Regex capturing the prefix of the grouped indices in the first group, like
+    /// `^(.+)-\d{4}\.\d{2}\.\d{2}$`. default the name before the last `-`.
+    #[builder(default)]
+    pub(crate) index_group_pattern: Option<String>,
+    /// Show an alias pointing to many indices, like the rollover write alias, in one row of the
+    /// alias table which is expanded to the indices.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) group_aliases: bool,
+    /// How much of the help bar at the bottom is shown on startup. default "expanded".
+    #[builder(default)]
+    pub(crate) help_bar: Option<HelpBar>,
+    /// IANA time zone of the timestamps like "Asia/Tokyo". default UTC.
+    #[builder(default)]
+    pub(crate) timezone: Option<String>,
+    /// Fraction of the follow interval randomly added or subtracted on each poll, so that
+    /// consoles started together do not hit the cluster at the same time. 0 disables it.
+    /// default 0.1.
+    #[builder(default)]
+    pub(crate) poll_jitter: Option<f64>,
+}
+
+impl Config {
+    /// Load the config from the toml file, or yaml/json file for other extensions.
+    pub fn from_path(path: impl AsRef<Path>) -> error_stack::Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .into_report()
+            .change_context(ConfigError::Read)
+            .attach_printable_lazy(|| path.display().to_string())?;
+
+        let mut config: Config = parse(path, &content)
+            .map_err(|err| error_stack::report!(ConfigError::Parse).attach_printable(err))
+            .attach_printable_lazy(|| path.display().to_string())?;
+        config.source = Some(path.to_owned());
+        Ok(config)
+    }
+
+    /// Whether to enter the alternate screen, `NO_ALTERNATE_SCREEN` env disables it over the config.
+    pub fn alternate_screen(&self) -> bool {
+        if std::env::var_os(NO_ALTERNATE_SCREEN_ENV).is_some() {
+            return false;
+        }
+        self.alternate_screen.unwrap_or(true)
+    }
+
+    /// Reopen where the last session left off instead of the defaults in the config.
+    pub fn with_session_state(mut self, state: SessionState) -> Self {
+        if state.cluster.is_some() {
+            self.default_cluster = state.cluster;
+        }
+        if state.resource.is_some() {
+            self.default_resource = state.resource;
+        }
+        if state.index_filter.is_some() {
+            self.default_index_filter = state.index_filter;
+        }
+        if state.index_sort.is_some() {
+            self.index_default_sort = state.index_sort;
+        }
+        if state.help_bar.is_some() {
+            self.help_bar = state.help_bar;
+        }
+        self
+    }
+
+    /// Start on the target instead of the defaults in the config.
+    pub fn with_startup_target(mut self, target: StartupTarget) -> Self {
+        if target.cluster.is_some() {
+            self.default_cluster = target.cluster;
+        }
+        if target.resource.is_some() {
+            self.default_resource = target.resource;
+        }
+        if target.index_filter.is_some() {
+            self.default_index_filter = target.index_filter;
+        }
+        self
+    }
+}
+
+/// Where the operator left off, saved to `state_file` on quit.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SessionState {
+    pub(crate) cluster: Option<String>,
+    pub(crate) resource: Option<String>,
+    /// The name of the active index filter preset.
+    pub(crate) index_filter: Option<String>,
+    pub(crate) index_sort: Option<IndexSort>,
+    pub(crate) help_bar: Option<HelpBar>,
+}
+
+impl SessionState {
+    /// Missing or corrupt file is ignored to start fresh.
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        let path = path.as_ref();
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                tracing::warn!(path = %path.display(), "Read state file {err}");
+                return None;
+            }
+        };
+        match serde_json::from_str(&content) {
+            Ok(state) => Some(state),
+            Err(err) => {
+                tracing::warn!(path = %path.display(), "Ignore corrupt state file {err}");
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> error_stack::Result<(), ConfigError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .into_report()
+                .change_context(ConfigError::Write)
+                .attach_printable_lazy(|| dir.display().to_string())?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .into_report()
+            .change_context(ConfigError::Write)?;
+        fs::write(path, content)
+            .into_report()
+            .change_context(ConfigError::Write)
+            .attach_printable_lazy(|| path.display().to_string())
+    }
+}
+
+/// Where the tui starts, given on the command line so that a shell alias opens a specific view.
+#[derive(Clone, Debug, Default, TypedBuilder)]
+pub struct StartupTarget {
+    #[builder(default)]
+    pub(crate) cluster: Option<String>,
+    #[builder(default)]
+    pub(crate) resource: Option<String>,
+    /// The name of `index_filters` or an index name pattern.
+    #[builder(default)]
+    pub(crate) index_filter: Option<String>,
+}
+
+impl StartupTarget {
+    pub fn with_index_filter(mut self, filter: impl Into<String>) -> Self {
+        self.index_filter = Some(filter.into());
+        self
+    }
+}
+
+/// Parse "cluster" or "cluster/resource" like "prod/index".
+impl FromStr for StartupTarget {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_owned());
+        let (cluster, resource) = match s.split_once('/') {
+            Some((cluster, resource)) => (non_empty(cluster), non_empty(resource)),
+            None => (non_empty(s), None),
+        };
+        Ok(StartupTarget {
+            cluster,
+            resource,
+            index_filter: None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexSort {
+    #[default]
+    Name,
+    /// Red, yellow then green, by name within the same health.
+    Health,
+    /// Largest first.
+    StoreSize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HelpBar {
+    /// Key bindings and the transport status.
+    #[default]
+    Expanded,
+    /// Only the transport status line, like the in-flight count.
+    Minimal,
+    Hidden,
+}
+
+impl HelpBar {
+    pub(crate) fn next(self) -> Self {
+        match self {
+            HelpBar::Expanded => HelpBar::Minimal,
+            HelpBar::Minimal => HelpBar::Hidden,
+            HelpBar::Hidden => HelpBar::Expanded,
+        }
+    }
+}
+
+/// Column of the index table, unknown names fail to parse the config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum IndexColumn {
+    Index,
+    Health,
+    Status,
+    Primary,
+    Replica,
+    /// Primary and replica shards as "pri/rep".
+    Shards,
+    DocsCount,
+    DocsDeleted,
+    StoreSize,
+    PrimaryStoreSize,
+    Uuid,
+}
+
+impl IndexColumn {
+    pub(crate) const ALL: &'static [IndexColumn] = &[
+        IndexColumn::Index,
+        IndexColumn::Health,
+        IndexColumn::Status,
+        IndexColumn::Primary,
+        IndexColumn::Replica,
+        IndexColumn::DocsCount,
+        IndexColumn::DocsDeleted,
+        IndexColumn::StoreSize,
+        IndexColumn::PrimaryStoreSize,
+        IndexColumn::Uuid,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteUnits {
+    /// Powers of 1024.
+    #[default]
+    Binary,
+    /// Powers of 1000.
+    Decimal,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteDisplay {
+    /// Like "1.2 GiB".
+    #[default]
+    Humanized,
+    /// Like "1.2 GiB (1288490188)".
+    Both,
+    /// Like "1288490188".
+    Raw,
+}
+
+impl ByteDisplay {
+    pub(crate) fn next(self) -> Self {
+        match self {
+            ByteDisplay::Humanized => ByteDisplay::Both,
+            ByteDisplay::Both => ByteDisplay::Raw,
+            ByteDisplay::Raw => ByteDisplay::Humanized,
+        }
+    }
+}
+
+/// Connectivity check of each cluster before rendering.
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder)]
+pub struct PreflightConfig {
+    /// Do not check connectivity on startup.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) skip: bool,
+    /// Abort startup if any cluster is unreachable, otherwise just log them.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) strict: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder)]
+pub struct ThemeConfig {
+    /// Store sizes in bytes from which the size is rendered white, yellow and red.
+    /// default 1GiB, 10GiB, 100GiB.
+    #[builder(default)]
+    pub(crate) store_size_thresholds: Option<[u64; 3]>,
+    /// Node heap and ram usage percents from which the usage is rendered yellow and red.
+    /// default 75, 90.
+    #[builder(default)]
+    pub(crate) node_memory_thresholds: Option<[u8; 2]>,
+    /// Percents of deleted docs in an index from which the deleted count is rendered yellow
+    /// and red, as candidates of force merge. default 20, 40.
+    #[builder(default)]
+    pub(crate) deleted_docs_thresholds: Option<[u8; 2]>,
+    /// Milliseconds of a request from which the latency is rendered white, yellow and red,
+    /// green below them. default 200, 1000, 3000.
+    #[builder(default)]
+    pub(crate) latency_thresholds: Option<[u64; 3]>,
 }
 
 #[derive(Clone, Debug, Deserialize, TypedBuilder)]
 pub struct ElasticsearchConfig {
     pub(crate) name: String,
-    #[allow(dead_code)]
     pub(crate) endpoint: Url,
+    /// Kibana of the cluster, the selected index is opened in its index management if given.
+    #[builder(default)]
+    pub(crate) kibana_url: Option<Url>,
+    /// Color of the cluster name to tell environments apart, like "red" for production.
+    /// Color names or "#rrggbb".
+    #[builder(default)]
+    pub(crate) label_color: Option<String>,
+    /// Written inline or `{ file = "/run/secrets/prod.toml" }` to load it from the file.
+    #[serde(deserialize_with = "deserialize_credential")]
     pub(crate) credential: ElasticsearchCredential,
+    /// Number of documents to preview in the index detail. default 5.
+    #[builder(default)]
+    pub(crate) sample_docs_size: Option<u32>,
+    /// Unit of the store sizes requested from `_cat/indices`. default "b".
+    #[builder(default)]
+    pub(crate) cat_bytes_unit: Option<CatBytesUnit>,
+    /// Index pattern to scope `_cat/indices` on clusters with many indices, like "logs-*".
+    /// default all indices.
+    #[builder(default)]
+    pub(crate) index_pattern: Option<String>,
+    /// Include the memory of unloaded segments in `_cat/indices`. default false.
+    #[builder(default)]
+    pub(crate) include_unloaded_segments: Option<bool>,
+    /// Headers sent with every request, like `X-Tenant` required by a gateway in front of the cluster.
+    #[builder(default)]
+    pub(crate) default_headers: Option<BTreeMap<String, String>>,
+    /// Major version of the cluster, "7" or "8", to send its compatible `Accept` and
+    /// `Content-Type`. default the headers of the client library.
+    #[builder(default)]
+    pub(crate) api_version: Option<String>,
+    /// Seconds to wait for the cluster to answer the reachability check on startup, shorter than
+    /// the 20 seconds given to each request to load large responses. default 5.
+    #[builder(default)]
+    pub(crate) ping_timeout_secs: Option<u64>,
+    /// Fail the response larger than this instead of loading it, like from an endpoint which is
+    /// not elasticsearch. default 256 MiB.
+    #[builder(default)]
+    pub(crate) max_response_bytes: Option<u64>,
+    /// Show the first bytes of the body which fails to parse, like an html error page of a
+    /// proxy. not shown by default.
+    #[builder(default)]
+    pub(crate) error_body_bytes: Option<usize>,
+    /// Rules flagging the shard counts of the indices in the index table. default none.
+    #[builder(default)]
+    pub(crate) index_warnings: Option<IndexWarningConfig>,
+}
+
+/// Best practices of the shard counts, the violating cells of the index table are colored.
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder)]
+pub struct IndexWarningConfig {
+    /// Flag indices with more primaries than this.
+    #[builder(default)]
+    pub(crate) max_primaries: Option<u32>,
+    /// Apply `max_primaries` only to the indices whose primary store size in bytes is below
+    /// this, since large indices need the shards. default all indices.
+    #[builder(default)]
+    pub(crate) small_index_bytes: Option<u64>,
+    /// Flag indices without replicas, like on a production cluster.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) require_replicas: bool,
+    /// Mark the flagged cells with a glyph in addition to the color.
+    #[serde(default)]
+    #[builder(default)]
+    pub(crate) glyph: bool,
+}
+
+impl IndexWarningConfig {
+    /// Counts which are not numbers, like of closed indices, are never flagged.
+    pub(crate) fn too_many_primaries(&self, pri: &str, pri_store_bytes: &str) -> bool {
+        let (max, pri) = match (self.max_primaries, pri.parse::<u32>()) {
+            (Some(max), Ok(pri)) => (max, pri),
+            _ => return false,
+        };
+        let small = match self.small_index_bytes {
+            Some(small) => matches!(pri_store_bytes.parse::<u64>(), Ok(bytes) if bytes < small),
+            None => true,
+        };
+        pri > max && small
+    }
+
+    pub(crate) fn missing_replicas(&self, rep: &str) -> bool {
+        self.require_replicas && rep.parse::<u32>() == Ok(0)
+    }
+}
+
+/// Coarser units are rounded down by elasticsearch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatBytesUnit {
+    #[default]
+    B,
+    Kb,
+    Mb,
+    Gb,
+    Tb,
+    Pb,
+}
+
+impl CatBytesUnit {
+    /// Value of the `bytes` query parameter.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            CatBytesUnit::B => "b",
+            CatBytesUnit::Kb => "kb",
+            CatBytesUnit::Mb => "mb",
+            CatBytesUnit::Gb => "gb",
+            CatBytesUnit::Tb => "tb",
+            CatBytesUnit::Pb => "pb",
+        }
+    }
+
+    /// Number of bytes in the unit.
+    pub(crate) fn bytes(self) -> u64 {
+        match self {
+            CatBytesUnit::B => 1,
+            CatBytesUnit::Kb => 1 << 10,
+            CatBytesUnit::Mb => 1 << 20,
+            CatBytesUnit::Gb => 1 << 30,
+            CatBytesUnit::Tb => 1 << 40,
+            CatBytesUnit::Pb => 1 << 50,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, TypedBuilder)]
 pub struct ElasticsearchCredential {
+    #[serde(default)]
+    #[builder(default)]
     pub(crate) username: String,
+    #[serde(default)]
+    #[builder(default)]
     pub(crate) password: String,
     pub(crate) cloud_id: Option<String>,
+    /// Sent as `Authorization: Bearer`, takes precedence over the basic auth.
+    #[builder(default)]
+    pub(crate) bearer_token: Option<String>,
+    /// Shell command printing a fresh bearer token, run when the client is built.
+    /// Takes precedence over `bearer_token` since static tokens expire.
+    #[builder(default)]
+    pub(crate) token_command: Option<String>,
+}
+
+fn deserialize_credential<'de, D>(deserializer: D) -> Result<ElasticsearchCredential, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_map(CredentialVisitor)
+}
+
+/// Visit the fields one by one instead of an untagged enum, which would hide the wrong field of
+/// the inline credential behind "did not match any variant".
+struct CredentialVisitor;
+
+impl<'de> Visitor<'de> for CredentialVisitor {
+    type Value = ElasticsearchCredential;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an inline credential or { file = path }")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut file: Option<PathBuf> = None;
+        let mut credential = ElasticsearchCredential::builder().cloud_id(None).build();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "file" => file = Some(map.next_value()?),
+                "username" => credential.username = map.next_value()?,
+                "password" => credential.password = map.next_value()?,
+                "cloud_id" => credential.cloud_id = map.next_value()?,
+                "bearer_token" => credential.bearer_token = map.next_value()?,
+                "token_command" => credential.token_command = map.next_value()?,
+                // unknown keys are ignored as the derived impl does.
+                _ => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+        match file {
+            Some(file) => load_credential_file(&file).map_err(de::Error::custom),
+            None => Ok(credential),
+        }
+    }
+}
+
+/// Load the credential from the toml file, or yaml/json file for other extensions.
+fn load_credential_file(path: &Path) -> Result<ElasticsearchCredential, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| format!("read credential file {}: {err}", path.display()))?;
+
+    parse(path, &content).map_err(|err| format!("parse credential file {}: {err}", path.display()))
+}
+
+/// Parse as toml if the extension says so, otherwise as yaml which also accepts json.
+fn parse<T: DeserializeOwned>(path: &Path, content: &str) -> Result<T, String> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(content).map_err(|err| err.to_string()),
+        _ => serde_yaml::from_str(content).map_err(|err| err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn startup_target_cluster() {
+        let target: StartupTarget = "prod".parse().unwrap();
+        assert_eq!(target.cluster.as_deref(), Some("prod"));
+        assert_eq!(target.resource, None);
+        assert_eq!(target.index_filter, None);
+    }
+
+    #[test]
+    fn startup_target_cluster_and_resource() {
+        let target: StartupTarget = "prod/index".parse().unwrap();
+        assert_eq!(target.cluster.as_deref(), Some("prod"));
+        assert_eq!(target.resource.as_deref(), Some("index"));
+    }
+
+    #[test]
+    fn startup_target_empty_parts() {
+        let target: StartupTarget = "/index".parse().unwrap();
+        assert_eq!(target.cluster, None);
+        assert_eq!(target.resource.as_deref(), Some("index"));
+
+        let target: StartupTarget = "prod/".parse().unwrap();
+        assert_eq!(target.cluster.as_deref(), Some("prod"));
+        assert_eq!(target.resource, None);
+
+        let target: StartupTarget = "".parse().unwrap();
+        assert_eq!(target.cluster, None);
+        assert_eq!(target.resource, None);
+    }
+
+    #[test]
+    fn startup_target_splits_on_first_slash() {
+        let target: StartupTarget = "prod/index/extra".parse().unwrap();
+        assert_eq!(target.cluster.as_deref(), Some("prod"));
+        assert_eq!(target.resource.as_deref(), Some("index/extra"));
+    }
+
+    /// Config of a cluster with the credential written in yaml.
+    fn parse_credential(credential: &str) -> Result<ElasticsearchCredential, String> {
+        let yaml = format!(
+            "elasticsearch:\n  - name: cluster-a\n    endpoint: https://localhost:9200\n    credential: {credential}\n"
+        );
+        parse::<Config>(Path::new("config.yaml"), &yaml)
+            .map(|config| config.elasticsearch.unwrap().remove(0).credential)
+    }
+
+    /// Write the content to a file unique to the test.
+    fn write_credential_file(name: &str, content: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("infra-console-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn credential_inline() {
+        let credential =
+            parse_credential(r#"{ username: elastic, password: secret, cloud_id: "a:b" }"#)
+                .unwrap();
+        assert_eq!(credential.username, "elastic");
+        assert_eq!(credential.password, "secret");
+        assert_eq!(credential.cloud_id.as_deref(), Some("a:b"));
+    }
+
+    #[test]
+    fn credential_inline_error_names_the_field() {
+        let err = parse_credential("{ username: elastic, password: [secret] }").unwrap_err();
+        assert!(err.contains("credential.password: invalid type"), "{err}");
+    }
+
+    #[test]
+    fn credential_from_file() {
+        let path = write_credential_file(
+            "credential.toml",
+            "username = \"elastic\"\npassword = \"secret\"\ncloud_id = \"a:b\"\n",
+        );
+        let credential = parse_credential(&format!("{{ file: {} }}", path.display()));
+        fs::remove_file(&path).unwrap();
+
+        let credential = credential.unwrap();
+        assert_eq!(credential.username, "elastic");
+        assert_eq!(credential.password, "secret");
+        assert_eq!(credential.cloud_id.as_deref(), Some("a:b"));
+    }
+
+    #[test]
+    fn credential_file_missing() {
+        let path = std::env::temp_dir().join("infra-console-missing-credential.yaml");
+        let err = parse_credential(&format!("{{ file: {} }}", path.display())).unwrap_err();
+        assert!(
+            err.contains(&format!("read credential file {}", path.display())),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn credential_file_malformed() {
+        let path = write_credential_file("malformed.yaml", "username: [elastic\n");
+        let err = parse_credential(&format!("{{ file: {} }}", path.display()));
+        fs::remove_file(&path).unwrap();
+
+        let err = err.unwrap_err();
+        assert!(
+            err.contains(&format!("parse credential file {}", path.display())),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn ping_timeout_of_yaml_and_toml() {
+        let yaml = r#"
+elasticsearch:
+  - name: cluster-a
+    endpoint: https://localhost:9200
+    credential:
+      cloud_id: "cluster-a:abc"
+    ping_timeout_secs: 2
+  - name: cluster-b
+    endpoint: https://localhost:9201
+    credential:
+      cloud_id: "cluster-b:abc"
+"#;
+        let config: Config = parse(Path::new("config.yaml"), yaml).unwrap();
+        let clusters = config.elasticsearch.unwrap();
+        assert_eq!(clusters[0].ping_timeout_secs, Some(2));
+        assert_eq!(clusters[1].ping_timeout_secs, None);
+
+        let toml = r#"
+[[elasticsearch]]
+name = "cluster-a"
+endpoint = "https://localhost:9200"
+ping_timeout_secs = 2
+
+[elasticsearch.credential]
+cloud_id = "cluster-a:abc"
+"#;
+        let config: Config = parse(Path::new("config.toml"), toml).unwrap();
+        assert_eq!(config.elasticsearch.unwrap()[0].ping_timeout_secs, Some(2));
+    }
 }
@@ -1,11 +1,19 @@
 use std::collections::HashMap;
 
 use error_stack::{Report, ResultExt};
+use futures::future;
+use itertools::Itertools;
 
 use crate::{
+    app::error_reason,
     client::elasticsearch::{
-        response::{CatAliases, CatIndices, ClusterHealth},
-        ElasticsearchClient, ElasticsearchClientError,
+        compatible_media_type, failure_reason,
+        response::{
+            AllocationExplain, CatAliases, CatIndices, CatNodes, ClusterHealth, ClusterSettings,
+            IlmPolicies, IndexMappings, IndexSettings, IndexTemplates, RawResponse, Search,
+            SnapshotRepositories, Snapshots, Tasks,
+        },
+        ElasticsearchClient, ElasticsearchClientError, CAT_NODES_COLUMNS,
     },
     config::ElasticsearchConfig,
     event::api::ApiHandleError,
@@ -14,9 +22,164 @@ use crate::{
 #[derive(Debug, Clone)]
 #[allow(clippy::enum_variant_names)]
 pub(crate) enum ElasticsearchRequestEvent {
-    FetchCluster { cluster_name: String },
-    FetchIndices { cluster_name: String },
-    FetchAliases { cluster_name: String },
+    FetchCluster {
+        cluster_name: String,
+    },
+    FetchIndices {
+        cluster_name: String,
+        /// Index pattern pushed down to `_cat/indices`, the configured pattern is used if `None`.
+        pattern: Option<String>,
+    },
+    FetchNodes {
+        cluster_name: String,
+    },
+    FetchAliases {
+        cluster_name: String,
+    },
+    FetchTemplates {
+        cluster_name: String,
+    },
+    FetchIlmPolicies {
+        cluster_name: String,
+    },
+    FetchRepositories {
+        cluster_name: String,
+    },
+    FetchSnapshots {
+        cluster_name: String,
+        repository: String,
+    },
+    FetchSampleDocs {
+        cluster_name: String,
+        index: String,
+        /// Offset of the page, 0 for the first page.
+        from: i64,
+    },
+    FetchIndexSettings {
+        cluster_name: String,
+        index: String,
+    },
+    FetchIndexMappings {
+        cluster_name: String,
+        index: String,
+    },
+    FetchClusterSettings {
+        cluster_name: String,
+    },
+    FetchAllocationExplain {
+        cluster_name: String,
+    },
+    FetchTasks {
+        cluster_name: String,
+    },
+    /// `GET` the path typed by the user, only reads are allowed.
+    RawRequest {
+        cluster_name: String,
+        path: String,
+    },
+    /// Search indices by name across all clusters.
+    SearchAllClusters {
+        pattern: String,
+    },
+    /// Health of every cluster for the dashboard.
+    FetchAllClusterHealth,
+}
+
+impl ElasticsearchRequestEvent {
+    /// Equivalent curl command of the request to the cluster, credentials are left to the shell.
+    /// `None` for requests across clusters.
+    pub(crate) fn to_curl(&self, config: &ElasticsearchConfig) -> Option<String> {
+        let (method, path, body) = self.request_line(config)?;
+
+        let mut curl = format!(
+            "curl -u \"$USER:$PASS\" -X {method} '{}/{path}'",
+            config.endpoint.as_str().trim_end_matches('/')
+        );
+        let media_type = match config.api_version.as_deref() {
+            Some(api_version) => {
+                let media_type = compatible_media_type(api_version);
+                curl.push_str(&format!(" -H 'Accept: {media_type}'"));
+                media_type
+            }
+            None => "application/json".to_owned(),
+        };
+        for (name, value) in config.default_headers.iter().flatten() {
+            curl.push_str(&format!(" -H '{name}: {value}'"));
+        }
+        if let Some(body) = body {
+            curl.push_str(&format!(" -H 'Content-Type: {media_type}' -d '{body}'"));
+        }
+        Some(curl)
+    }
+
+    /// Api path with the query like "/_cat/aliases?format=json", for scripts.
+    pub(crate) fn to_path(&self, config: &ElasticsearchConfig) -> Option<String> {
+        self.request_line(config)
+            .map(|(_, path, _)| format!("/{path}"))
+    }
+
+    /// Method, path without the leading slash and body of the request to one cluster.
+    fn request_line(
+        &self,
+        config: &ElasticsearchConfig,
+    ) -> Option<(&'static str, String, Option<&'static str>)> {
+        use ElasticsearchRequestEvent::*;
+        let line = match self {
+            FetchCluster { .. } => ("GET", "_cluster/health?level=cluster".to_owned(), None),
+            FetchIndices { pattern, .. } => {
+                let mut path = match pattern.as_deref().or(config.index_pattern.as_deref()) {
+                    Some(pattern) => format!("_cat/indices/{pattern}"),
+                    None => "_cat/indices".to_owned(),
+                };
+                path.push_str("?format=json&bytes=");
+                path.push_str(config.cat_bytes_unit.unwrap_or_default().as_str());
+                if config.include_unloaded_segments.unwrap_or(false) {
+                    path.push_str("&include_unloaded_segments=true");
+                }
+                ("GET", path, None)
+            }
+            FetchNodes { .. } => (
+                "GET",
+                format!("_cat/nodes?format=json&h={}", CAT_NODES_COLUMNS.join(",")),
+                None,
+            ),
+            FetchAliases { .. } => ("GET", "_cat/aliases?format=json".to_owned(), None),
+            FetchTemplates { .. } => ("GET", "_index_template".to_owned(), None),
+            FetchIlmPolicies { .. } => ("GET", "_ilm/policy".to_owned(), None),
+            FetchRepositories { .. } => ("GET", "_snapshot".to_owned(), None),
+            FetchSnapshots { repository, .. } => (
+                "GET",
+                format!("_snapshot/{repository}/_all?ignore_unavailable=true"),
+                None,
+            ),
+            FetchSampleDocs { index, from, .. } => (
+                "GET",
+                format!(
+                    "{index}/_search?from={from}&size={}",
+                    config.sample_docs_size.unwrap_or(5)
+                ),
+                None,
+            ),
+            FetchIndexSettings { index, .. } => ("GET", format!("{index}/_settings"), None),
+            FetchIndexMappings { index, .. } => ("GET", format!("{index}/_mapping"), None),
+            FetchClusterSettings { .. } => (
+                "GET",
+                "_cluster/settings?flat_settings=true".to_owned(),
+                None,
+            ),
+            FetchAllocationExplain { .. } => {
+                ("GET", "_cluster/allocation/explain".to_owned(), Some("{}"))
+            }
+            FetchTasks { .. } => (
+                "GET",
+                "_tasks?detailed=true&group_by=parents".to_owned(),
+                None,
+            ),
+            RawRequest { path, .. } => ("GET", path.trim_start_matches('/').to_owned(), None),
+            SearchAllClusters { .. } | FetchAllClusterHealth => return None,
+        };
+        Some(line)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -29,31 +192,169 @@ pub(crate) enum ElasticsearchResponseEvent {
         cluster_name: String,
         response: CatIndices,
     },
+    Nodes {
+        cluster_name: String,
+        response: CatNodes,
+    },
     Aliases {
         cluster_name: String,
         response: CatAliases,
     },
+    Templates {
+        cluster_name: String,
+        response: IndexTemplates,
+    },
+    IlmPolicies {
+        cluster_name: String,
+        /// None if ILM is not available on the cluster.
+        response: Option<IlmPolicies>,
+    },
+    Repositories {
+        cluster_name: String,
+        response: SnapshotRepositories,
+    },
+    Snapshots {
+        cluster_name: String,
+        repository: String,
+        response: Snapshots,
+    },
+    SampleDocs {
+        cluster_name: String,
+        index: String,
+        from: i64,
+        response: Search,
+    },
+    IndexSettings {
+        cluster_name: String,
+        index: String,
+        response: IndexSettings,
+    },
+    IndexMappings {
+        cluster_name: String,
+        index: String,
+        response: IndexMappings,
+    },
+    ClusterSettings {
+        cluster_name: String,
+        response: ClusterSettings,
+    },
+    AllocationExplain {
+        cluster_name: String,
+        /// `None` if all shards are assigned.
+        response: Option<AllocationExplain>,
+    },
+    Tasks {
+        cluster_name: String,
+        response: Tasks,
+    },
+    RawResponse {
+        cluster_name: String,
+        path: String,
+        response: RawResponse,
+    },
+    SearchAllClusters {
+        pattern: String,
+        /// Matched indices per cluster.
+        indices: Vec<(String, CatIndices)>,
+        outcome: FanOutOutcome,
+    },
+    AllClusterHealth {
+        healths: Vec<(String, ClusterHealth)>,
+        outcome: FanOutOutcome,
+    },
+}
+
+/// Clusters a request sent to all clusters succeeded or failed on.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FanOutOutcome {
+    pub(crate) ok: Vec<String>,
+    /// Clusters failed with the short reason, like "timeout".
+    pub(crate) failed: Vec<(String, String)>,
+}
+
+impl FanOutOutcome {
+    fn push<T>(
+        &mut self,
+        cluster_name: &str,
+        result: &error_stack::Result<T, ElasticsearchClientError>,
+    ) {
+        match result {
+            Ok(_) => self.ok.push(cluster_name.to_owned()),
+            Err(report) => {
+                tracing::warn!(%cluster_name, "{report:?}");
+                self.failed
+                    .push((cluster_name.to_owned(), failure_reason(report)));
+            }
+        }
+    }
+
+    /// Like "3 ok, 2 failed (auth, timeout)".
+    pub(crate) fn summary(&self) -> String {
+        let mut summary = format!("{} ok", self.ok.len());
+        if !self.failed.is_empty() {
+            let reasons = self
+                .failed
+                .iter()
+                .map(|(_, reason)| reason.as_str())
+                .sorted()
+                .dedup()
+                .join(", ");
+            summary.push_str(&format!(", {} failed ({reasons})", self.failed.len()));
+        }
+        summary
+    }
 }
 
 pub(crate) struct ElasticsearchApiHandler {
     clients: HashMap<String, ElasticsearchClient>,
+    /// Clusters whose client failed to build with the reason, the others are still usable.
+    build_failures: HashMap<String, String>,
 }
 
 impl ElasticsearchApiHandler {
-    pub(crate) fn new(
-        configs: Vec<ElasticsearchConfig>,
-    ) -> error_stack::Result<Self, ElasticsearchClientError> {
-        let clients = configs
-            .into_iter()
-            .map(ElasticsearchClient::new)
-            .collect::<Result<Vec<ElasticsearchClient>, _>>()?
-            .into_iter()
-            .fold(HashMap::new(), |mut h, client| {
-                h.insert(client.name().to_owned(), client);
-                h
-            });
-
-        Ok(ElasticsearchApiHandler { clients })
+    /// Build the clients independently so that one bad config does not stop the others.
+    pub(crate) async fn new(configs: Vec<ElasticsearchConfig>) -> Self {
+        // token commands of the clusters run concurrently.
+        let built = future::join_all(configs.into_iter().map(|config| async move {
+            let name = config.name.clone();
+            (name, ElasticsearchClient::new(config).await)
+        }))
+        .await;
+
+        let mut clients = HashMap::new();
+        let mut build_failures = HashMap::new();
+        for (name, result) in built {
+            match result {
+                Ok(client) => {
+                    clients.insert(client.name().to_owned(), client);
+                }
+                Err(report) => {
+                    tracing::error!(cluster_name=%name, "{report:?}");
+                    build_failures.insert(name, error_reason(&report));
+                }
+            }
+        }
+
+        ElasticsearchApiHandler {
+            clients,
+            build_failures,
+        }
+    }
+
+    pub(crate) fn build_failures(&self) -> &HashMap<String, String> {
+        &self.build_failures
+    }
+
+    /// Ping the clusters whose client is built.
+    pub(crate) async fn ping(
+        &self,
+    ) -> Vec<(String, error_stack::Result<(), ElasticsearchClientError>)> {
+        future::join_all(
+            self.clients
+                .values()
+                .map(|client| async move { (client.name().to_owned(), client.ping().await) }),
+        )
+        .await
     }
 
     pub(crate) async fn handle(
@@ -61,6 +362,7 @@ impl ElasticsearchApiHandler {
         req: ElasticsearchRequestEvent,
     ) -> error_stack::Result<ElasticsearchResponseEvent, ApiHandleError> {
         use ElasticsearchRequestEvent::*;
+
         match req {
             FetchCluster { cluster_name } => {
                 let client = self.lookup_cluster(&cluster_name)?;
@@ -76,13 +378,16 @@ impl ElasticsearchApiHandler {
                     })
                     .change_context(ApiHandleError::Elasticsearch)
             }
-            FetchIndices { cluster_name } => {
+            FetchIndices {
+                cluster_name,
+                pattern,
+            } => {
                 let client = self.lookup_cluster(&cluster_name)?;
 
-                tracing::info!("Fetch indices...");
+                tracing::info!(?pattern, "Fetch indices...");
 
                 client
-                    .cat_indices()
+                    .cat_indices(pattern.as_deref())
                     .await
                     .map(|indices| ElasticsearchResponseEvent::Indices {
                         cluster_name,
@@ -90,6 +395,20 @@ impl ElasticsearchApiHandler {
                     })
                     .change_context(ApiHandleError::Elasticsearch)
             }
+            FetchNodes { cluster_name } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!("Fetch nodes...");
+
+                client
+                    .cat_nodes()
+                    .await
+                    .map(|nodes| ElasticsearchResponseEvent::Nodes {
+                        cluster_name,
+                        response: nodes,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
             FetchAliases { cluster_name } => {
                 let client = self.lookup_cluster(&cluster_name)?;
 
@@ -104,16 +423,332 @@ impl ElasticsearchApiHandler {
                     })
                     .change_context(ApiHandleError::Elasticsearch)
             }
+            FetchTemplates { cluster_name } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!("Fetch index templates...");
+
+                client
+                    .get_index_templates()
+                    .await
+                    .map(|templates| ElasticsearchResponseEvent::Templates {
+                        cluster_name,
+                        response: templates,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchIlmPolicies { cluster_name } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!("Fetch ilm policies...");
+
+                client
+                    .get_ilm_policies()
+                    .await
+                    .map(|policies| ElasticsearchResponseEvent::IlmPolicies {
+                        cluster_name,
+                        response: policies,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchRepositories { cluster_name } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!("Fetch snapshot repositories...");
+
+                client
+                    .get_repositories()
+                    .await
+                    .map(|repositories| ElasticsearchResponseEvent::Repositories {
+                        cluster_name,
+                        response: repositories,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchSnapshots {
+                cluster_name,
+                repository,
+            } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!(%repository, "Fetch snapshots...");
+
+                client
+                    .get_snapshots(&repository)
+                    .await
+                    .map(|snapshots| ElasticsearchResponseEvent::Snapshots {
+                        cluster_name,
+                        repository,
+                        response: snapshots,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchSampleDocs {
+                cluster_name,
+                index,
+                from,
+            } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!(%index, from, "Fetch sample docs...");
+
+                client
+                    .sample_docs(&index, from)
+                    .await
+                    .map(|docs| ElasticsearchResponseEvent::SampleDocs {
+                        cluster_name,
+                        index,
+                        from,
+                        response: docs,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchIndexSettings {
+                cluster_name,
+                index,
+            } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!(%index, "Fetch index settings...");
+
+                client
+                    .get_index_settings(&index)
+                    .await
+                    .map(|settings| ElasticsearchResponseEvent::IndexSettings {
+                        cluster_name,
+                        index,
+                        response: settings,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchIndexMappings {
+                cluster_name,
+                index,
+            } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!(%index, "Fetch index mappings...");
+
+                client
+                    .get_index_mappings(&index)
+                    .await
+                    .map(|mappings| ElasticsearchResponseEvent::IndexMappings {
+                        cluster_name,
+                        index,
+                        response: mappings,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchClusterSettings { cluster_name } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!("Fetch cluster settings...");
+
+                client
+                    .get_cluster_settings()
+                    .await
+                    .map(|settings| ElasticsearchResponseEvent::ClusterSettings {
+                        cluster_name,
+                        response: settings,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchTasks { cluster_name } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!("Fetch tasks...");
+
+                client
+                    .get_tasks()
+                    .await
+                    .map(|tasks| ElasticsearchResponseEvent::Tasks {
+                        cluster_name,
+                        response: tasks,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            FetchAllocationExplain { cluster_name } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!("Fetch allocation explain...");
+
+                client
+                    .allocation_explain()
+                    .await
+                    .map(|explain| ElasticsearchResponseEvent::AllocationExplain {
+                        cluster_name,
+                        response: explain,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            RawRequest { cluster_name, path } => {
+                let client = self.lookup_cluster(&cluster_name)?;
+
+                tracing::info!(%path, "Raw get...");
+
+                client
+                    .raw_get(&path)
+                    .await
+                    .map(|response| ElasticsearchResponseEvent::RawResponse {
+                        cluster_name,
+                        path,
+                        response,
+                    })
+                    .change_context(ApiHandleError::Elasticsearch)
+            }
+            SearchAllClusters { pattern } => {
+                tracing::info!(%pattern, "Search indices across clusters...");
+
+                // all indices regardless of the configured pattern, matched below by substring.
+                let results =
+                    future::join_all(self.clients.iter().map(|(name, client)| async move {
+                        (name.clone(), client.cat_indices(Some("*")).await)
+                    }))
+                    .await;
+
+                let mut indices = Vec::new();
+                let mut outcome = self.fan_out_outcome();
+                for (cluster_name, result) in results {
+                    outcome.push(&cluster_name, &result);
+                    if let Ok(cat_indices) = result {
+                        indices.push((
+                            cluster_name,
+                            cat_indices
+                                .into_iter()
+                                .filter(|index| index.index.contains(pattern.as_str()))
+                                .collect(),
+                        ));
+                    }
+                }
+
+                Ok(ElasticsearchResponseEvent::SearchAllClusters {
+                    pattern,
+                    indices,
+                    outcome,
+                })
+            }
+            FetchAllClusterHealth => {
+                tracing::info!("Fetch health of all clusters...");
+
+                let results =
+                    future::join_all(self.clients.iter().map(|(name, client)| async move {
+                        (name.clone(), client.get_cluster_health().await)
+                    }))
+                    .await;
+
+                let mut healths = Vec::new();
+                let mut outcome = self.fan_out_outcome();
+                for (cluster_name, result) in results {
+                    outcome.push(&cluster_name, &result);
+                    if let Ok(health) = result {
+                        healths.push((cluster_name, health));
+                    }
+                }
+
+                Ok(ElasticsearchResponseEvent::AllClusterHealth { healths, outcome })
+            }
         }
     }
 
+    /// Outcome starting with the clusters whose client is not built.
+    fn fan_out_outcome(&self) -> FanOutOutcome {
+        let mut outcome = FanOutOutcome::default();
+        for cluster_name in self.build_failures.keys() {
+            outcome
+                .failed
+                .push((cluster_name.clone(), "build".to_owned()));
+        }
+        outcome
+    }
+
     fn lookup_cluster(
         &self,
         name: &str,
     ) -> error_stack::Result<&ElasticsearchClient, ApiHandleError> {
+        if let Some(reason) = self.build_failures.get(name) {
+            return Err(Report::new(ApiHandleError::ClusterUnavailable))
+                .attach_printable(reason.clone());
+        }
         self.clients
             .get(name)
             .ok_or_else(|| Report::new(ApiHandleError::Elasticsearch))
             .attach_printable("client not found by name: {name}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ElasticsearchCredential;
+
+    fn config(name: &str, cloud_id: Option<&str>) -> ElasticsearchConfig {
+        ElasticsearchConfig::builder()
+            .name(name.to_owned())
+            .endpoint("https://localhost:9200".parse().unwrap())
+            .credential(
+                ElasticsearchCredential::builder()
+                    .cloud_id(cloud_id.map(str::to_owned))
+                    .build(),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn bad_config_keeps_the_other_clients() {
+        let handler = ElasticsearchApiHandler::new(vec![
+            config("good", Some("good:bG9jYWxob3N0JGVzJGtpYmFuYQ==")),
+            // only the cloud id is supported.
+            config("bad", None),
+        ])
+        .await;
+
+        assert_eq!(handler.clients.keys().collect::<Vec<_>>(), vec!["good"]);
+        assert_eq!(handler.build_failures().len(), 1);
+        let reason = &handler.build_failures()["bad"];
+        assert!(
+            reason.contains("currently only cloud id credential supported"),
+            "{reason}"
+        );
+    }
+
+    #[test]
+    fn fan_out_summary_all_ok() {
+        let outcome = FanOutOutcome {
+            ok: vec!["a".to_owned(), "b".to_owned()],
+            failed: vec![],
+        };
+        assert_eq!(outcome.summary(), "2 ok");
+    }
+
+    #[test]
+    fn fan_out_summary_dedups_reasons() {
+        let outcome = FanOutOutcome {
+            ok: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+            failed: vec![
+                ("d".to_owned(), "timeout".to_owned()),
+                ("e".to_owned(), "auth".to_owned()),
+                ("f".to_owned(), "timeout".to_owned()),
+            ],
+        };
+        assert_eq!(outcome.summary(), "3 ok, 3 failed (auth, timeout)");
+    }
+
+    #[test]
+    fn fan_out_push_keeps_the_reason() {
+        let mut outcome = FanOutOutcome::default();
+        outcome.push("a", &Ok::<_, error_stack::Report<_>>(()));
+        outcome.push::<()>(
+            "b",
+            &Err(error_stack::report!(
+                ElasticsearchClientError::ResponseTooLarge
+            )),
+        );
+
+        assert_eq!(outcome.ok, vec!["a".to_owned()]);
+        assert_eq!(
+            outcome.failed,
+            vec![("b".to_owned(), "response too large".to_owned())]
+        );
+        assert_eq!(outcome.summary(), "1 ok, 1 failed (response too large)");
+    }
+}
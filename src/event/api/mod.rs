@@ -1,12 +1,18 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
-use error_stack::ResultExt;
+use error_stack::Report;
 use thiserror::Error;
 use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::field;
 use tracing_futures::Instrument;
 
 use crate::{
     app::RequestId,
+    client::elasticsearch::ElasticsearchClientError,
     event::api::elasticsearch::{
         ElasticsearchApiHandler, ElasticsearchRequestEvent, ElasticsearchResponseEvent,
     },
@@ -29,6 +35,8 @@ pub(crate) enum RequestEvent {
 #[derive(Debug)]
 pub(crate) struct ResponseEnvelope {
     pub(crate) request_id: RequestId,
+    /// Request the response is for, to tell the view which data failed to fetch.
+    pub(crate) request: RequestEvent,
     pub(crate) result: error_stack::Result<ResponseEvent, ApiHandleError>,
 }
 
@@ -37,27 +45,82 @@ pub(crate) enum ResponseEvent {
     Elasticsearch(ElasticsearchResponseEvent),
 }
 
+/// Attempts of the requests being retried, shared between the handler and the transport stats.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RetryAttempts(Arc<Mutex<HashMap<RequestId, u32>>>);
+
+impl RetryAttempts {
+    fn set(&self, request_id: RequestId, attempt: u32) {
+        self.0.lock().unwrap().insert(request_id, attempt);
+    }
+
+    fn remove(&self, request_id: RequestId) {
+        self.0.lock().unwrap().remove(&request_id);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+
+    /// The furthest attempt among the retrying requests.
+    pub(crate) fn max_attempt(&self) -> Option<u32> {
+        self.0.lock().unwrap().values().max().copied()
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct ApiHandler {
     elasticsearch: Arc<ElasticsearchApiHandler>,
+    retries: RetryAttempts,
 }
 
 #[derive(Clone, Debug, Error)]
 pub(crate) enum ApiHandleError {
     #[error("elasticsearch api error")]
     Elasticsearch,
+    #[error("cluster is unavailable")]
+    ClusterUnavailable,
+}
+
+/// The other side of the channel between the view and the api handler is gone, like on shutdown
+/// or config reload.
+#[derive(Clone, Copy, Debug, Error)]
+pub(crate) enum ChannelClosed {
+    #[error("request channel closed")]
+    Request,
+    #[error("response channel closed")]
+    Response,
 }
 
 impl ApiHandler {
-    pub(crate) fn new(
-        elasticsearch_configs: Vec<ElasticsearchConfig>,
-    ) -> error_stack::Result<Self, ApiHandleError> {
-        Ok(Self {
-            elasticsearch: Arc::new(
-                ElasticsearchApiHandler::new(elasticsearch_configs)
-                    .change_context(ApiHandleError::Elasticsearch)?,
-            ),
-        })
+    /// Including the first attempt.
+    pub(crate) const MAX_ATTEMPTS: u32 = 3;
+    /// Doubled on each retry.
+    const BACKOFF: Duration = Duration::from_millis(500);
+
+    /// Build the clients once, to be shared by the preflight check and the transport.
+    pub(crate) async fn new(elasticsearch_configs: Vec<ElasticsearchConfig>) -> Self {
+        Self {
+            elasticsearch: Arc::new(ElasticsearchApiHandler::new(elasticsearch_configs).await),
+            retries: RetryAttempts::default(),
+        }
+    }
+
+    pub(crate) fn with_retries(mut self, retries: RetryAttempts) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Clusters whose client failed to build with the reason.
+    pub(crate) fn build_failures(&self) -> HashMap<String, String> {
+        self.elasticsearch.build_failures().clone()
+    }
+
+    /// Check the clusters whose client is built are reachable.
+    pub(crate) async fn ping(
+        &self,
+    ) -> Vec<(String, error_stack::Result<(), ElasticsearchClientError>)> {
+        self.elasticsearch.ping().await
     }
 
     pub(crate) async fn run(
@@ -68,12 +131,22 @@ impl ApiHandler {
         tracing::info!("ApiHandler running...");
 
         loop {
-            let req = match rx.recv().await {
-                Some(req) => {
-                    tracing::debug!(?req, "Receive");
-                    req
+            let req = tokio::select! {
+                req = rx.recv() => match req {
+                    Some(req) => {
+                        tracing::debug!(?req, "Receive");
+                        req
+                    }
+                    None => {
+                        tracing::info!("{}", ChannelClosed::Request);
+                        break;
+                    }
+                },
+                // nobody would receive the responses.
+                _ = res_tx.closed() => {
+                    tracing::warn!("{}", ChannelClosed::Response);
+                    break;
                 }
-                None => break,
             };
 
             self.dispatch(req, res_tx.clone());
@@ -86,26 +159,116 @@ impl ApiHandler {
         // Cloning the entire handler is inefficient, should find a better way.
         let this = self.clone();
         let task = async move {
-            let result = match e.event {
+            let request = e.event.clone();
+            let started = Instant::now();
+            let (result, span) = match e.event {
                 RequestEvent::Elasticsearch(req) => {
-                    let span = tracing::info_span!("dispatch",api="elasticsearch",request=?req,id=?e.request_id);
-                    this.elasticsearch
-                        .handle(req)
-                        .instrument(span)
+                    let span = tracing::info_span!(
+                        "dispatch",
+                        api = "elasticsearch",
+                        request = ?req,
+                        id = %e.request_id,
+                        elapsed_ms = field::Empty,
+                        outcome = field::Empty,
+                    );
+                    let result = this
+                        .handle_elasticsearch(e.request_id, req)
+                        .instrument(span.clone())
                         .await
-                        .map(ResponseEvent::Elasticsearch)
+                        .map(ResponseEvent::Elasticsearch);
+                    (result, span)
                 }
             };
+            // logged when the span closes.
+            span.record("elapsed_ms", started.elapsed().as_millis() as u64);
+            span.record("outcome", if result.is_ok() { "ok" } else { "err" });
             // TODO: to chain by futures;
-            res_tx
+            let sent = res_tx
                 .send(ResponseEnvelope {
                     request_id: e.request_id,
+                    request,
                     result,
                 })
-                .await
-                .ok();
+                .await;
+            if sent.is_err() {
+                let report = Report::new(ChannelClosed::Response)
+                    .attach_printable(format!("drop the response of {}", e.request_id));
+                tracing::warn!("{report:?}");
+            }
         };
 
         tokio::spawn(task);
     }
+
+    /// Retry the request failed to reach the cluster with backoff.
+    async fn handle_elasticsearch(
+        &self,
+        request_id: RequestId,
+        req: ElasticsearchRequestEvent,
+    ) -> error_stack::Result<ElasticsearchResponseEvent, ApiHandleError> {
+        let mut attempt = 1;
+        loop {
+            match self.elasticsearch.handle(req.clone()).await {
+                Err(report)
+                    if attempt < Self::MAX_ATTEMPTS
+                        && matches!(
+                            report.downcast_ref::<ElasticsearchClientError>(),
+                            Some(ElasticsearchClientError::ApiRequest)
+                        )
+                        // the cluster answered with an error status, retrying will not help.
+                        && report
+                            .downcast_ref::<::elasticsearch::Error>()
+                            .and_then(|err| err.status_code())
+                            .is_none() =>
+                {
+                    let backoff = Self::BACKOFF * 2u32.pow(attempt - 1);
+                    attempt += 1;
+                    tracing::warn!(attempt, ?backoff, "Retry request {report:?}");
+                    self.retries.set(request_id, attempt);
+                    tokio::time::sleep(backoff).await;
+                }
+                result => {
+                    self.retries.remove(request_id);
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn run_ends_when_the_response_receiver_is_dropped() {
+        let (req_tx, req_rx) = mpsc::channel(1);
+        let (res_tx, res_rx) = mpsc::channel(1);
+        drop(res_rx);
+
+        let handler = tokio::spawn(ApiHandler::new(Vec::new()).await.run(req_rx, res_tx));
+
+        // the request sender is still alive, so only the closed response channel ends the loop.
+        tokio::time::timeout(Duration::from_secs(1), handler)
+            .await
+            .expect("handler keeps running")
+            .expect("handler panicked");
+        drop(req_tx);
+    }
+
+    #[tokio::test]
+    async fn run_ends_when_the_request_sender_is_dropped() {
+        let (req_tx, req_rx) = mpsc::channel::<RequestEnvelope>(1);
+        let (res_tx, _res_rx) = mpsc::channel(1);
+        drop(req_tx);
+
+        let handler = tokio::spawn(ApiHandler::new(Vec::new()).await.run(req_rx, res_tx));
+
+        tokio::time::timeout(Duration::from_secs(1), handler)
+            .await
+            .expect("handler keeps running")
+            .expect("handler panicked");
+    }
 }
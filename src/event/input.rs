@@ -1,3 +1,5 @@
+use std::io;
+
 pub use crossterm::event::EventStream;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use Event::*;
@@ -7,11 +9,15 @@ use crate::view::{
     component::{
         elasticsearch::{
             ElasticsearchComponentKind,
-            ElasticsearchComponentKind::{AliasTable, IndexTable, ResourceList},
+            ElasticsearchComponentKind::{
+                AliasTable, ClusterList, DashboardTable, GlobalSearch, IlmPolicyList, IndexPalette,
+                IndexTable, NodeTable, RawGet, RepositoryList, ResourceList, SettingTable,
+                SnapshotTable, TaskTable, TemplateTable,
+            },
         },
         ComponentKind, ResourceKind,
     },
-    Navigate, ViewState,
+    ColumnAdjust, Navigate, TextEdit, ViewState,
 };
 
 pub(crate) trait InputQuery {
@@ -77,6 +83,52 @@ pub(crate) enum Command {
     UnfocusComponent,
     FocusComponent(ComponentKind),
     NavigateComponent(ComponentKind, Navigate),
+    EnterComponent(ComponentKind),
+    LeaveComponent,
+    EditComponent(ComponentKind, TextEdit),
+    CopyError,
+    ToggleLeftPanel,
+    /// Focus the next visible component, or the previous one if true.
+    CycleFocus(bool),
+    CycleFilterPreset(ComponentKind),
+    ToggleDensity,
+    OpenInBrowser,
+    CycleSort(ComponentKind),
+    AdjustColumn(ComponentKind, ColumnAdjust),
+    /// Expand or collapse the selected row in place.
+    ToggleExpand(ComponentKind),
+    /// Select the next item starting with the character.
+    JumpToInitial(ComponentKind, char),
+    ReloadConfig,
+    /// Open the config file in `$EDITOR` and reload it once the editor exits.
+    EditConfig,
+    ToggleHiddenIndices,
+    /// Hide cluster and index names for screenshots.
+    ToggleRedact,
+    /// Count the docs and store size of primaries only or all copies.
+    ToggleSizeScope,
+    /// Show the store sizes humanized, with the raw bytes or as raw bytes.
+    CycleByteDisplay,
+    ToggleCompactJson,
+    /// Render the timestamps as their age like "2h ago" instead of the time.
+    ToggleRelativeTimestamps,
+    /// Summarize the indices sharing a name prefix in one row.
+    ToggleIndexGroups,
+    /// Show the outcome per cluster of the latest request to all clusters.
+    ToggleFanOut,
+    /// Show the health transitions of the clusters in this session.
+    ToggleHealthLog,
+    ToggleFollow,
+    ResetView,
+    /// Fetch the next page of the entered component.
+    LoadMore,
+    CopyCurl,
+    /// Copy the api path of the selected resource without the endpoint.
+    CopyPath,
+    /// Show the version and build info.
+    ToggleAbout,
+    /// Shrink, hide or expand the help bar.
+    CycleHelpBar,
 }
 
 pub(crate) struct InputHandler {
@@ -88,17 +140,19 @@ impl InputHandler {
         Self { event_stream }
     }
 
-    pub(crate) async fn read(&mut self, state: &ViewState) -> Command {
+    /// Read inputs until one makes a command, quit if the input stream is closed.
+    pub(crate) async fn read(&mut self, state: &ViewState) -> io::Result<Command> {
         use futures::StreamExt;
 
         loop {
-            let input = self
-                .event_stream
-                .next()
-                .await
-                .transpose()
-                .unwrap()
-                .expect("Keyboard input stream closed unexpectedly");
+            let input = match self.event_stream.next().await {
+                Some(input) => input?,
+                // stdin is closed, like the end of piped input.
+                None => {
+                    tracing::info!("Input stream closed");
+                    return Ok(Command::QuitApp);
+                }
+            };
 
             tracing::trace!(?input, "Read input");
 
@@ -109,7 +163,7 @@ impl InputHandler {
             if let Some(command) = self.handle(input, state) {
                 tracing::debug!(?command, "Handle");
 
-                return command;
+                return Ok(command);
             }
         }
     }
@@ -117,13 +171,142 @@ impl InputHandler {
     fn handle(&self, input: Event, state: &ViewState) -> Option<Command> {
         use Command::*;
         use ResourceKind::*;
+        if let Some(component) = state
+            .focused_component
+            .filter(|component| component.accepts_text())
+        {
+            return self.handle_text_input(input, component);
+        }
+
+        // type to jump takes every letter and digit while the cluster list is focused, even the
+        // shortcuts, so that every cluster is reachable by its initial. arrows move the selection.
+        if let (
+            Some(component @ ComponentKind::Elasticsearch(ClusterList)),
+            None,
+            Key(KeyEvent {
+                code: Char(c),
+                modifiers,
+                ..
+            }),
+        ) = (state.focused_component, state.entered_component, &input)
+        {
+            if c.is_alphanumeric() && !modifiers.contains(KeyModifiers::CONTROL) {
+                return Some(JumpToInitial(component, *c));
+            }
+        }
+
         if input.should_quit() {
             return Some(QuitApp);
         }
+        if let Key(KeyEvent {
+            code: Char('o'),
+            modifiers,
+            ..
+        }) = input
+        {
+            if modifiers.contains(KeyModifiers::CONTROL) {
+                return Some(ReloadConfig);
+            }
+        }
+        if let Key(KeyEvent {
+            code: Char('e'),
+            modifiers,
+            ..
+        }) = input
+        {
+            if modifiers.contains(KeyModifiers::CONTROL) {
+                return Some(EditConfig);
+            }
+        }
+        if let Key(KeyEvent {
+            code: Char('p'),
+            modifiers,
+            ..
+        }) = input
+        {
+            if modifiers.contains(KeyModifiers::CONTROL)
+                && state.selected_resource == Some(Elasticsearch)
+            {
+                return Some(FocusComponent(ComponentKind::Elasticsearch(IndexPalette)));
+            }
+        }
+        if let Key(KeyEvent {
+            code: Char('b'),
+            modifiers,
+            ..
+        }) = input
+        {
+            if modifiers.contains(KeyModifiers::CONTROL)
+                && state.selected_resource == Some(Elasticsearch)
+            {
+                return Some(ToggleLeftPanel);
+            }
+        }
+        if let Key(KeyEvent {
+            code: Char('l'),
+            modifiers,
+            ..
+        }) = input
+        {
+            if modifiers.contains(KeyModifiers::CONTROL)
+                && state.selected_resource == Some(Elasticsearch)
+            {
+                return Some(ResetView);
+            }
+        }
 
         #[allow(clippy::single_match)]
         match input.key_code() {
+            Some(KeyCode::Char('v')) => return Some(ToggleAbout),
+            Some(KeyCode::Char('?')) => return Some(CycleHelpBar),
+            Some(KeyCode::Esc) if state.show_about => return Some(ToggleAbout),
+            Some(KeyCode::Esc) if state.entered_component.is_some() => return Some(LeaveComponent),
             Some(KeyCode::Esc) => return Some(UnfocusComponent),
+            Some(KeyCode::Tab) if state.entered_component.is_none() => {
+                return Some(CycleFocus(false))
+            }
+            Some(KeyCode::BackTab) if state.entered_component.is_none() => {
+                return Some(CycleFocus(true))
+            }
+            Some(KeyCode::Char('w')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleDensity)
+            }
+            Some(KeyCode::Char('b')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(OpenInBrowser)
+            }
+            Some(KeyCode::Char('.')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleHiddenIndices)
+            }
+            Some(KeyCode::Char('F')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleFollow)
+            }
+            Some(KeyCode::Char('R')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleRedact)
+            }
+            Some(KeyCode::Char('P')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleSizeScope)
+            }
+            Some(KeyCode::Char('B')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(CycleByteDisplay)
+            }
+            Some(KeyCode::Char('J')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleCompactJson)
+            }
+            Some(KeyCode::Char('A')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleRelativeTimestamps)
+            }
+            Some(KeyCode::Char('E')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleFanOut)
+            }
+            Some(KeyCode::Char('H')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(ToggleHealthLog)
+            }
+            Some(KeyCode::Char('C')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(CopyCurl)
+            }
+            Some(KeyCode::Char('Y')) if state.selected_resource == Some(Elasticsearch) => {
+                return Some(CopyPath)
+            }
             _ => (),
         }
 
@@ -137,23 +320,132 @@ impl InputHandler {
                 (Some(Elasticsearch), Some(KeyCode::Char('e'))) => {
                     return Some(FocusComponent(ComponentKind::Elasticsearch(ResourceList)))
                 }
+                (Some(Elasticsearch), Some(Char('d'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(DashboardTable)))
+                }
+                (Some(Elasticsearch), Some(Char('n'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(NodeTable)))
+                }
                 (Some(Elasticsearch), Some(Char('i'))) => {
                     return Some(FocusComponent(ComponentKind::Elasticsearch(IndexTable)))
                 }
                 (Some(Elasticsearch), Some(Char('a'))) => {
                     return Some(FocusComponent(ComponentKind::Elasticsearch(AliasTable)))
                 }
+                (Some(Elasticsearch), Some(Char('t'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(TemplateTable)))
+                }
+                (Some(Elasticsearch), Some(Char('p'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(IlmPolicyList)))
+                }
+                (Some(Elasticsearch), Some(Char('o'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(RepositoryList)))
+                }
+                (Some(Elasticsearch), Some(Char('s'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(SnapshotTable)))
+                }
+                (Some(Elasticsearch), Some(Char('g'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(SettingTable)))
+                }
+                (Some(Elasticsearch), Some(Char('T'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(TaskTable)))
+                }
+                (Some(Elasticsearch), Some(Char('/'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(GlobalSearch)))
+                }
+                (Some(Elasticsearch), Some(Char(':'))) => {
+                    return Some(FocusComponent(ComponentKind::Elasticsearch(RawGet)))
+                }
                 (_, Some(KeyCode::Char('r'))) => {
                     return Some(FocusComponent(ComponentKind::ResourceTab))
                 }
+                (_, Some(KeyCode::Char('y'))) => return Some(CopyError),
                 _ => (),
             },
             Some(component) => {
                 if let Some(navigate) = input.navigate() {
-                    return Some(NavigateComponent(component, navigate));
+                    return Some(NavigateComponent(
+                        state.entered_component.unwrap_or(component),
+                        navigate,
+                    ));
+                }
+                if state.entered_component.is_none() && input.key_code() == Some(&KeyCode::Enter) {
+                    return Some(EnterComponent(component));
+                }
+                if state.entered_component
+                    == Some(ComponentKind::Elasticsearch(
+                        ElasticsearchComponentKind::IndexDetail,
+                    ))
+                    && input.key_code() == Some(&KeyCode::Char('m'))
+                {
+                    return Some(LoadMore);
+                }
+                if input.key_code() == Some(&KeyCode::Char('f')) {
+                    return Some(CycleFilterPreset(component));
+                }
+                if input.key_code() == Some(&KeyCode::Char('S')) {
+                    return Some(CycleSort(component));
+                }
+                if matches!(
+                    component,
+                    ComponentKind::Elasticsearch(IndexTable | AliasTable)
+                ) && state.entered_component.is_none()
+                    && input.key_code() == Some(&KeyCode::Char('x'))
+                {
+                    return Some(ToggleExpand(component));
+                }
+                if component == ComponentKind::Elasticsearch(IndexTable)
+                    && state.entered_component.is_none()
+                    && input.key_code() == Some(&KeyCode::Char('G'))
+                {
+                    return Some(ToggleIndexGroups);
+                }
+                if component == ComponentKind::Elasticsearch(IndexTable)
+                    && state.entered_component.is_none()
+                {
+                    let adjust = match input.key_code() {
+                        Some(Char('<')) => Some(ColumnAdjust::Prev),
+                        Some(Char('>')) => Some(ColumnAdjust::Next),
+                        Some(Char(']')) => Some(ColumnAdjust::Widen),
+                        Some(Char('[')) => Some(ColumnAdjust::Narrow),
+                        Some(Char('=')) => Some(ColumnAdjust::Reset),
+                        _ => None,
+                    };
+                    if let Some(adjust) = adjust {
+                        return Some(AdjustColumn(component, adjust));
+                    }
+                }
+                // jump back to the resource tab without unfocusing first.
+                if matches!(component, ComponentKind::Elasticsearch(_))
+                    && input.key_code() == Some(&KeyCode::Char('r'))
+                {
+                    return Some(FocusComponent(ComponentKind::ResourceTab));
                 }
             }
         }
         None
     }
+
+    /// Handle input for the component typing text, where character keys are not shortcuts.
+    fn handle_text_input(&self, input: Event, component: ComponentKind) -> Option<Command> {
+        use Command::*;
+        match input {
+            Key(KeyEvent {
+                code: Char('c') | Char('d'),
+                modifiers,
+                ..
+            }) if modifiers.contains(KeyModifiers::CONTROL) => Some(QuitApp),
+            Key(KeyEvent { code, .. }) => match code {
+                Esc => Some(UnfocusComponent),
+                Enter => Some(EditComponent(component, TextEdit::Submit)),
+                Backspace => Some(EditComponent(component, TextEdit::Pop)),
+                Tab => Some(EditComponent(component, TextEdit::CycleMode)),
+                Char(c) => Some(EditComponent(component, TextEdit::Push(c))),
+                Up => Some(NavigateComponent(component, Navigate::Up)),
+                Down => Some(NavigateComponent(component, Navigate::Down)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
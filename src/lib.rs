@@ -6,4 +6,8 @@ mod view;
 
 pub mod client;
 
-pub use config::{Config, ElasticsearchConfig, ElasticsearchCredential};
+pub use config::{
+    ByteDisplay, ByteUnits, CatBytesUnit, Config, ConfigError, ElasticsearchConfig,
+    ElasticsearchCredential, HelpBar, IndexColumn, IndexSort, IndexWarningConfig, PreflightConfig,
+    SessionState, StartupTarget, ThemeConfig,
+};
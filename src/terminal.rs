@@ -1,10 +1,17 @@
 use std::{
-    io,
+    env, fs,
+    io::{self, Write},
     ops::{Deref, DerefMut},
+    path::Path,
+    process::{self, Stdio},
 };
 
-use crossterm::terminal::{
-    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+use base64::Engine;
+use crossterm::{
+    cursor::MoveTo,
+    terminal::{
+        self, disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+    },
 };
 use error_stack::{IntoReport, ResultExt};
 use thiserror::Error;
@@ -18,16 +25,18 @@ pub type Terminal = tui::Terminal<CrosstermBackend<io::Stdout>>;
 
 pub struct TerminalGuard {
     inner: Terminal,
+    alternate_screen: bool,
 }
 
 pub fn init() -> error_stack::Result<TerminalGuard, TerminalError> {
-    enable_raw_mode()
-        .into_report()
-        .change_context(TerminalError {})?;
+    init_with_alternate_screen(true)
+}
 
-    crossterm::execute!(io::stdout(), EnterAlternateScreen)
-        .into_report()
-        .change_context(TerminalError {})?;
+/// Without the alternate screen the view is drawn over the main screen and kept in the scrollback.
+pub fn init_with_alternate_screen(
+    alternate_screen: bool,
+) -> error_stack::Result<TerminalGuard, TerminalError> {
+    enter_terminal(alternate_screen)?;
 
     let backend = CrosstermBackend::new(io::stdout());
     let inner = Terminal::new(backend)
@@ -37,11 +46,100 @@ pub fn init() -> error_stack::Result<TerminalGuard, TerminalError> {
     // configure panic hook to display panic message to user.
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic| {
-        reset_terminal().ok();
+        reset_terminal(alternate_screen).ok();
         original_hook(panic);
     }));
 
-    Ok(TerminalGuard { inner })
+    Ok(TerminalGuard {
+        inner,
+        alternate_screen,
+    })
+}
+
+/// Copy text to the clipboard with OSC 52 escape sequence, which also works over ssh.
+pub fn copy_to_clipboard(text: &str) -> error_stack::Result<(), TerminalError> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = io::stdout();
+
+    write!(stdout, "\x1b]52;c;{encoded}\x07")
+        .and_then(|_| stdout.flush())
+        .into_report()
+        .change_context(TerminalError {})
+}
+
+/// Ring the bell to draw attention to the terminal.
+pub fn ring_bell() -> error_stack::Result<(), TerminalError> {
+    let mut stdout = io::stdout();
+
+    write!(stdout, "\x07")
+        .and_then(|_| stdout.flush())
+        .into_report()
+        .change_context(TerminalError {})
+}
+
+/// Open the url with the default browser of the platform.
+pub fn open_in_browser(url: &str) -> error_stack::Result<(), TerminalError> {
+    open::that(url)
+        .into_report()
+        .change_context(TerminalError {})
+        .attach_printable_lazy(|| format!("open {url}"))
+}
+
+/// Open the file with `$EDITOR`, or vi if not set, and wait until the editor exits.
+pub fn edit_file(path: &Path) -> error_stack::Result<(), TerminalError> {
+    let editor = env::var("EDITOR")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_owned());
+
+    // through the shell so that the editor can have arguments like `code --wait`.
+    let mut command = process::Command::new("sh");
+    command
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("sh")
+        .arg(path);
+
+    // stdin may be piped, terminal editors need the controlling terminal.
+    if let Ok(tty) = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+    {
+        if let Ok(tty_out) = tty.try_clone() {
+            command.stdin(Stdio::from(tty)).stdout(Stdio::from(tty_out));
+        }
+    }
+
+    let status = command
+        .status()
+        .into_report()
+        .change_context(TerminalError {})
+        .attach_printable_lazy(|| format!("run {editor}"))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(error_stack::report!(TerminalError {}))
+            .attach_printable(format!("{editor} exited with {status}"))
+    }
+}
+
+impl TerminalGuard {
+    /// Hand the terminal over to the process run in `f`, like an editor, and take it back
+    /// even if `f` fails.
+    pub fn suspend<T>(&mut self, f: impl FnOnce() -> T) -> error_stack::Result<T, TerminalError> {
+        reset_terminal(self.alternate_screen)?;
+        let output = f();
+        enter_terminal(self.alternate_screen)?;
+
+        // the process has drawn over the screen, draw the whole view on the next frame.
+        self.inner
+            .clear()
+            .into_report()
+            .change_context(TerminalError {})?;
+        Ok(output)
+    }
 }
 
 impl Deref for TerminalGuard {
@@ -59,18 +157,42 @@ impl DerefMut for TerminalGuard {
 
 impl Drop for TerminalGuard {
     fn drop(&mut self) {
-        if let Err(error) = reset_terminal() {
+        if let Err(error) = reset_terminal(self.alternate_screen) {
             tracing::error!(%error, "running terminal cleanup");
         }
     }
 }
 
-fn reset_terminal() -> error_stack::Result<(), TerminalError> {
-    disable_raw_mode()
+fn enter_terminal(alternate_screen: bool) -> error_stack::Result<(), TerminalError> {
+    enable_raw_mode()
         .into_report()
         .change_context(TerminalError {})?;
 
-    crossterm::execute!(io::stdout(), LeaveAlternateScreen)
+    if alternate_screen {
+        crossterm::execute!(io::stdout(), EnterAlternateScreen)
+            .into_report()
+            .change_context(TerminalError {})?;
+    }
+    Ok(())
+}
+
+fn reset_terminal(alternate_screen: bool) -> error_stack::Result<(), TerminalError> {
+    disable_raw_mode()
         .into_report()
-        .change_context(TerminalError {})
+        .change_context(TerminalError {})?;
+
+    if alternate_screen {
+        crossterm::execute!(io::stdout(), LeaveAlternateScreen)
+            .into_report()
+            .change_context(TerminalError {})
+    } else {
+        // continue the shell below the last view.
+        let (_, rows) = terminal::size()
+            .into_report()
+            .change_context(TerminalError {})?;
+        crossterm::execute!(io::stdout(), MoveTo(0, rows.saturating_sub(1)))
+            .and_then(|_| writeln!(io::stdout()))
+            .into_report()
+            .change_context(TerminalError {})
+    }
 }
@@ -1,28 +1,95 @@
-use std::collections::HashMap;
+use std::{
+    cmp,
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-use tui::{style::Color, text::Text};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use regex::Regex;
+use tui::{
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+};
 
 use crate::{
-    client::elasticsearch::response::{CatAlias, CatAliases, CatIndex, CatIndices, ClusterHealth},
-    view::style::Styled,
+    client::elasticsearch::response::{
+        AllocationExplain, CatAlias, CatAliases, CatIndex, CatIndices, CatNode, CatNodes,
+        ClusterHealth, ClusterSettings, IlmPolicies, IlmPolicy, IndexMappings, IndexSettings,
+        IndexTemplateEntry, IndexTemplates, RawResponse, Search, Snapshot, SnapshotRepositories,
+        SnapshotRepository, Snapshots, Task, Tasks,
+    },
+    event::api::elasticsearch::FanOutOutcome,
+    view::{
+        component::{elasticsearch::ElasticsearchResourceKind, filter::TableFilter},
+        style::Styled,
+    },
+    ByteDisplay, ByteUnits, IndexSort,
 };
 
 #[derive(Debug)]
 pub(super) struct Data {
     clusters: HashMap<String, ClusterData>,
+    global_search: Option<GlobalSearchResult>,
+    raw_response: Option<RawResult>,
+    /// Latest request sent to all clusters and its outcome.
+    fan_out: Option<(&'static str, FanOutOutcome)>,
+    /// Health transitions of the clusters in this session, the oldest first.
+    health_log: VecDeque<HealthChange>,
 }
 
 impl Data {
     pub(super) fn new() -> Self {
         Self {
             clusters: HashMap::new(),
+            global_search: None,
+            raw_response: None,
+            fan_out: None,
+            health_log: VecDeque::new(),
         }
     }
 }
 
 impl Data {
-    pub(super) fn update_cluster_health(&mut self, cluster_name: String, health: ClusterHealth) {
-        self.cluster_data_mut(cluster_name).health = Some(health);
+    /// Return true if the health turned red from another status, not on the first fetch.
+    pub(super) fn update_cluster_health(
+        &mut self,
+        cluster_name: String,
+        health: ClusterHealth,
+    ) -> bool {
+        let data = self.cluster_data_mut(cluster_name.clone());
+        let turned_red = health.status == "red"
+            && matches!(&data.health, Some(previous) if previous.status != "red");
+        let change = match &data.health {
+            Some(previous) if previous.status != health.status => Some(HealthChange {
+                cluster_name,
+                from: previous.status.clone(),
+                to: health.status.clone(),
+                at: SystemTime::now(),
+            }),
+            _ => None,
+        };
+        if data.health_history.len() == HEALTH_HISTORY_SIZE {
+            data.health_history.pop_front();
+        }
+        data.health_history.push_back(HealthSample {
+            status: health.status.clone(),
+            active_shards_percent: health.active_shards_percent_as_number,
+        });
+        data.health = Some(health);
+        data.mark_ok(ElasticsearchResourceKind::Cluster);
+
+        if let Some(change) = change {
+            if self.health_log.len() == HEALTH_LOG_SIZE {
+                self.health_log.pop_front();
+            }
+            self.health_log.push_back(change);
+        }
+        turned_red
+    }
+
+    pub(super) fn get_health_log(&self) -> &VecDeque<HealthChange> {
+        &self.health_log
     }
 
     pub(super) fn get_cluster_health(&self, cluster_name: &str) -> Option<&ClusterHealth> {
@@ -31,22 +98,106 @@ impl Data {
             .and_then(|c| c.health.as_ref())
     }
 
+    /// Health of the cluster so far, the oldest first.
+    pub(super) fn get_health_history(&self, cluster_name: &str) -> Option<&VecDeque<HealthSample>> {
+        self.clusters.get(cluster_name).map(|c| &c.health_history)
+    }
+
     pub(super) fn update_indices(&mut self, cluster_name: String, indices: CatIndices) {
-        self.cluster_data_mut(cluster_name).indices = Some(indices);
+        let data = self.cluster_data_mut(cluster_name);
+        // keep highlighting the previous changes if nothing changed since then.
+        if let Some(diff) = data
+            .indices
+            .as_ref()
+            .map(|previous| IndexDiff::new(previous, &indices))
+            .filter(|diff| !diff.is_empty())
+        {
+            data.index_diff = Some(diff);
+        }
+        data.indices = Some(indices);
+        data.mark_ok(ElasticsearchResourceKind::Index);
     }
 
-    pub(super) fn get_visible_indices(
+    /// Changes of the indices by the latest fetch while they are highlighted.
+    pub(super) fn get_index_diff(&self, cluster_name: &str) -> Option<&IndexDiff> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.index_diff.as_ref())
+            .filter(|diff| diff.remaining().is_some())
+    }
+
+    /// Indices gone by the latest fetch matching the filter, like `get_visible_indices`.
+    pub(super) fn get_disappeared_indices<'a>(
+        &'a self,
+        cluster_name: &str,
+        filter: Option<&'a TableFilter>,
+        show_hidden: bool,
+    ) -> impl Iterator<Item = &'a CatIndex> + 'a {
+        self.get_index_diff(cluster_name)
+            .into_iter()
+            .flat_map(|diff| diff.disappeared.iter())
+            .filter(move |index| match filter {
+                Some(filter) => filter.matches(&index.index),
+                None => true,
+            })
+            .filter(move |index| show_hidden || !is_hidden(index))
+    }
+
+    /// Indices matching the filter, dot-prefixed system indices are included only if `show_hidden`.
+    pub(super) fn get_visible_indices<'a>(
+        &'a self,
+        cluster_name: &str,
+        filter: Option<&'a TableFilter>,
+        show_hidden: bool,
+    ) -> Option<impl Iterator<Item = &'a CatIndex> + 'a> {
+        self.get_filtered_indices(cluster_name, filter)
+            .map(move |indices| indices.filter(move |index| show_hidden || !is_hidden(index)))
+    }
+
+    /// Number of the dot-prefixed indices matching the filter.
+    pub(super) fn count_hidden_indices(
         &self,
         cluster_name: &str,
-    ) -> Option<impl Iterator<Item = &CatIndex>> {
+        filter: Option<&TableFilter>,
+    ) -> usize {
+        self.get_filtered_indices(cluster_name, filter)
+            .map(|indices| indices.filter(|index| is_hidden(index)).count())
+            .unwrap_or(0)
+    }
+
+    fn get_filtered_indices<'a>(
+        &'a self,
+        cluster_name: &str,
+        filter: Option<&'a TableFilter>,
+    ) -> Option<impl Iterator<Item = &'a CatIndex> + 'a> {
         self.clusters
             .get(cluster_name)
             .and_then(|c| c.indices.as_ref())
-            .map(|indices| indices.iter().filter(|index| !index.index.starts_with('.')))
+            .map(move |indices| {
+                indices.iter().filter(move |index| match filter {
+                    Some(filter) => filter.matches(&index.index),
+                    None => true,
+                })
+            })
+    }
+
+    pub(super) fn update_nodes(&mut self, cluster_name: String, mut nodes: CatNodes) {
+        nodes.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        let data = self.cluster_data_mut(cluster_name);
+        data.nodes = Some(nodes);
+        data.mark_ok(ElasticsearchResourceKind::Node);
+    }
+
+    pub(super) fn get_nodes(&self, cluster_name: &str) -> Option<&[CatNode]> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.nodes.as_deref())
     }
 
     pub(super) fn update_aliases(&mut self, cluster_name: String, aliases: CatAliases) {
-        self.cluster_data_mut(cluster_name).aliases = Some(aliases);
+        let data = self.cluster_data_mut(cluster_name);
+        data.aliases = Some(aliases);
+        data.mark_ok(ElasticsearchResourceKind::Alias);
     }
 
     pub(super) fn get_visible_aliases(
@@ -59,6 +210,289 @@ impl Data {
             .map(|aliases| aliases.iter().filter(|alias| !alias.alias.starts_with('.')))
     }
 
+    pub(super) fn update_templates(&mut self, cluster_name: String, templates: IndexTemplates) {
+        let data = self.cluster_data_mut(cluster_name);
+        data.templates = Some(templates);
+        data.mark_ok(ElasticsearchResourceKind::Template);
+    }
+
+    pub(super) fn get_visible_templates(
+        &self,
+        cluster_name: &str,
+    ) -> Option<impl Iterator<Item = &IndexTemplateEntry>> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.templates.as_ref())
+            .map(|templates| {
+                templates
+                    .index_templates
+                    .iter()
+                    .filter(|template| !template.name.starts_with('.'))
+            })
+    }
+
+    pub(super) fn update_ilm_policies(
+        &mut self,
+        cluster_name: String,
+        policies: Option<IlmPolicies>,
+    ) {
+        let data = self.cluster_data_mut(cluster_name);
+        data.ilm_policies = Some(policies);
+        data.mark_ok(ElasticsearchResourceKind::Ilm);
+    }
+
+    /// Returns `Some(None)` if the cluster does not provide ILM.
+    pub(super) fn get_visible_ilm_policies(
+        &self,
+        cluster_name: &str,
+    ) -> Option<Option<impl Iterator<Item = (&String, &IlmPolicy)>>> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.ilm_policies.as_ref())
+            .map(|policies| {
+                policies
+                    .as_ref()
+                    .map(|policies| policies.iter().filter(|(name, _)| !name.starts_with('.')))
+            })
+    }
+
+    pub(super) fn update_repositories(
+        &mut self,
+        cluster_name: String,
+        repositories: SnapshotRepositories,
+    ) {
+        let data = self.cluster_data_mut(cluster_name);
+        data.repositories = Some(repositories);
+        data.mark_ok(ElasticsearchResourceKind::Snapshot);
+    }
+
+    pub(super) fn get_repositories(
+        &self,
+        cluster_name: &str,
+    ) -> Option<impl Iterator<Item = (&String, &SnapshotRepository)>> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.repositories.as_ref())
+            .map(|repositories| repositories.iter())
+    }
+
+    pub(super) fn update_snapshots(
+        &mut self,
+        cluster_name: String,
+        repository: String,
+        snapshots: Snapshots,
+    ) {
+        self.cluster_data_mut(cluster_name)
+            .snapshots
+            .insert(repository, snapshots);
+    }
+
+    pub(super) fn get_snapshots(
+        &self,
+        cluster_name: &str,
+        repository: &str,
+    ) -> Option<impl Iterator<Item = &Snapshot>> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.snapshots.get(repository))
+            .map(|snapshots| snapshots.snapshots.iter())
+    }
+
+    /// Replace the documents by the first page, append the following pages.
+    pub(super) fn update_sample_docs(
+        &mut self,
+        cluster_name: String,
+        index: String,
+        from: i64,
+        docs: Search,
+    ) {
+        let sample_docs = &mut self.cluster_data_mut(cluster_name).sample_docs;
+        match sample_docs.get_mut(&index) {
+            Some(loaded) if from > 0 => {
+                loaded.hits.total = docs.hits.total;
+                loaded.hits.hits.extend(docs.hits.hits);
+            }
+            _ => {
+                sample_docs.insert(index, docs);
+            }
+        }
+    }
+
+    pub(super) fn get_sample_docs(&self, cluster_name: &str, index: &str) -> Option<&Search> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.sample_docs.get(index))
+    }
+
+    pub(super) fn update_index_settings(
+        &mut self,
+        cluster_name: String,
+        index: String,
+        settings: IndexSettings,
+    ) {
+        self.cluster_data_mut(cluster_name)
+            .index_settings
+            .insert(index, settings);
+    }
+
+    pub(super) fn get_index_settings(
+        &self,
+        cluster_name: &str,
+        index: &str,
+    ) -> Option<&IndexSettings> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.index_settings.get(index))
+    }
+
+    pub(super) fn update_index_mappings(
+        &mut self,
+        cluster_name: String,
+        index: String,
+        mappings: IndexMappings,
+    ) {
+        self.cluster_data_mut(cluster_name)
+            .index_mappings
+            .insert(index, mappings);
+    }
+
+    pub(super) fn get_index_mappings(
+        &self,
+        cluster_name: &str,
+        index: &str,
+    ) -> Option<&IndexMappings> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.index_mappings.get(index))
+    }
+
+    pub(super) fn update_allocation_explain(
+        &mut self,
+        cluster_name: String,
+        explain: Option<AllocationExplain>,
+    ) {
+        self.cluster_data_mut(cluster_name).allocation_explain = Some(explain);
+    }
+
+    /// Returns `Some(None)` if all shards are assigned.
+    pub(super) fn get_allocation_explain(
+        &self,
+        cluster_name: &str,
+    ) -> Option<Option<&AllocationExplain>> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.allocation_explain.as_ref())
+            .map(Option::as_ref)
+    }
+
+    pub(super) fn update_cluster_settings(
+        &mut self,
+        cluster_name: String,
+        settings: ClusterSettings,
+    ) {
+        let data = self.cluster_data_mut(cluster_name);
+        data.settings = Some(settings);
+        data.mark_ok(ElasticsearchResourceKind::Settings);
+    }
+
+    /// Returns (scope, key, value), persistent settings come first.
+    pub(super) fn get_cluster_settings(
+        &self,
+        cluster_name: &str,
+    ) -> Option<impl Iterator<Item = (&'static str, &String, &serde_json::Value)>> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.settings.as_ref())
+            .map(|settings| {
+                let persistent = settings
+                    .persistent
+                    .iter()
+                    .map(|(key, value)| ("persistent", key, value));
+                let transient = settings
+                    .transient
+                    .iter()
+                    .map(|(key, value)| ("transient", key, value));
+                persistent.chain(transient)
+            })
+    }
+
+    /// Parent tasks are kept, the longest running first.
+    pub(super) fn update_tasks(&mut self, cluster_name: String, tasks: Tasks) {
+        let mut tasks: Vec<Task> = tasks.tasks.into_values().collect();
+        tasks.sort_unstable_by_key(|task| cmp::Reverse(task.running_time_in_nanos));
+        let data = self.cluster_data_mut(cluster_name);
+        data.tasks = Some(tasks);
+        data.mark_ok(ElasticsearchResourceKind::Task);
+    }
+
+    pub(super) fn get_tasks(&self, cluster_name: &str) -> Option<&[Task]> {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.tasks.as_deref())
+    }
+
+    pub(super) fn update_global_search(&mut self, result: GlobalSearchResult) {
+        self.global_search = Some(result);
+    }
+
+    pub(super) fn get_global_search(&self) -> Option<&GlobalSearchResult> {
+        self.global_search.as_ref()
+    }
+
+    pub(super) fn update_fan_out(&mut self, request: &'static str, outcome: FanOutOutcome) {
+        self.fan_out = Some((request, outcome));
+    }
+
+    pub(super) fn get_fan_out(&self) -> Option<&(&'static str, FanOutOutcome)> {
+        self.fan_out.as_ref()
+    }
+
+    pub(super) fn update_raw_response(&mut self, result: RawResult) {
+        self.raw_response = Some(result);
+    }
+
+    pub(super) fn get_raw_response(&self) -> Option<&RawResult> {
+        self.raw_response.as_ref()
+    }
+
+    pub(super) fn update_fetch_status(
+        &mut self,
+        cluster_name: String,
+        resource: ElasticsearchResourceKind,
+        status: FetchStatus,
+    ) {
+        let data = self.cluster_data_mut(cluster_name);
+        if let FetchStatus::Err(_) = status {
+            data.last_err.insert(resource, Instant::now());
+        }
+        data.statuses.insert(resource, status);
+    }
+
+    /// Time since the last successful fetch if the resource failed to refresh after it,
+    /// the data shown is the one fetched then.
+    pub(super) fn stale_since(
+        &self,
+        cluster_name: &str,
+        resource: ElasticsearchResourceKind,
+    ) -> Option<Duration> {
+        let data = self.clusters.get(cluster_name)?;
+        let last_ok = data.last_ok.get(&resource)?;
+        let last_err = data.last_err.get(&resource)?;
+        (last_err > last_ok).then(|| last_ok.elapsed())
+    }
+
+    pub(super) fn get_fetch_status(
+        &self,
+        cluster_name: &str,
+        resource: ElasticsearchResourceKind,
+    ) -> FetchStatus {
+        self.clusters
+            .get(cluster_name)
+            .and_then(|c| c.statuses.get(&resource))
+            .cloned()
+            .unwrap_or_default()
+    }
+
     fn cluster_data_mut(&mut self, cluster_name: String) -> &mut ClusterData {
         self.clusters
             .entry(cluster_name)
@@ -66,46 +500,469 @@ impl Data {
     }
 }
 
+/// How long the changes of the indices are highlighted after a fetch.
+const INDEX_DIFF_DURATION: Duration = Duration::from_secs(5);
+
+/// Change of an index since the previous fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum IndexChange {
+    Appeared,
+    /// Docs count or health changed.
+    Changed,
+    Disappeared,
+}
+
+/// Indices changed between the previous and the latest fetch.
+#[derive(Debug, Clone)]
+pub(super) struct IndexDiff {
+    at: Instant,
+    changes: HashMap<String, IndexChange>,
+    /// Shown at the bottom of the table until the highlight fades.
+    disappeared: Vec<CatIndex>,
+}
+
+impl IndexDiff {
+    fn new(previous: &CatIndices, current: &CatIndices) -> Self {
+        let previous: HashMap<&str, &CatIndex> = previous
+            .iter()
+            .map(|index| (index.index.as_str(), index))
+            .collect();
+        let changes = current
+            .iter()
+            .filter_map(|index| match previous.get(index.index.as_str()) {
+                None => Some((index.index.clone(), IndexChange::Appeared)),
+                Some(prev)
+                    if prev.docs_count != index.docs_count || prev.health != index.health =>
+                {
+                    Some((index.index.clone(), IndexChange::Changed))
+                }
+                Some(_) => None,
+            })
+            .collect();
+        let disappeared = previous
+            .values()
+            .filter(|prev| current.iter().all(|index| index.index != prev.index))
+            .map(|prev| (*prev).clone())
+            .collect();
+
+        Self {
+            at: Instant::now(),
+            changes,
+            disappeared,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.disappeared.is_empty()
+    }
+
+    pub(super) fn change(&self, index: &str) -> Option<IndexChange> {
+        self.changes.get(index).copied()
+    }
+
+    /// Time left until the highlight fades, `None` once it has faded.
+    pub(super) fn remaining(&self) -> Option<Duration> {
+        INDEX_DIFF_DURATION.checked_sub(self.at.elapsed())
+    }
+}
+
+/// Number of health transitions kept across the clusters.
+const HEALTH_LOG_SIZE: usize = 100;
+
+/// Health status of the cluster changed between polls, like green to yellow.
+#[derive(Debug, Clone)]
+pub(super) struct HealthChange {
+    pub(super) cluster_name: String,
+    pub(super) from: String,
+    pub(super) to: String,
+    pub(super) at: SystemTime,
+}
+
+/// Number of health samples kept per cluster for the follow mode.
+const HEALTH_HISTORY_SIZE: usize = 120;
+
+#[derive(Debug, Clone)]
+pub(super) struct HealthSample {
+    pub(super) status: String,
+    pub(super) active_shards_percent: f64,
+}
+
 #[derive(Debug, Default, Clone)]
 pub(super) struct ClusterData {
     health: Option<ClusterHealth>,
+    health_history: VecDeque<HealthSample>,
     indices: Option<CatIndices>,
+    index_diff: Option<IndexDiff>,
+    nodes: Option<CatNodes>,
     aliases: Option<CatAliases>,
+    templates: Option<IndexTemplates>,
+    ilm_policies: Option<Option<IlmPolicies>>,
+    repositories: Option<SnapshotRepositories>,
+    snapshots: HashMap<String, Snapshots>,
+    sample_docs: HashMap<String, Search>,
+    index_settings: HashMap<String, IndexSettings>,
+    index_mappings: HashMap<String, IndexMappings>,
+    settings: Option<ClusterSettings>,
+    allocation_explain: Option<Option<AllocationExplain>>,
+    tasks: Option<Vec<Task>>,
+    statuses: HashMap<ElasticsearchResourceKind, FetchStatus>,
+    last_ok: HashMap<ElasticsearchResourceKind, Instant>,
+    last_err: HashMap<ElasticsearchResourceKind, Instant>,
 }
 
-pub(super) struct ClusterHealthFormatter<'a>(pub(super) &'a ClusterHealth, pub(super) &'a Styled);
-
-impl<'a> From<ClusterHealthFormatter<'a>> for tui::text::Text<'a> {
-    fn from(this: ClusterHealthFormatter<'a>) -> Self {
-        let v = vec![
-            this.1.key_value_spans("cluster_name", &this.0.cluster_name),
-            this.1.key_value_spans("status", &this.0.status),
-            this.1.key_value_spans("nodes", this.0.number_of_nodes),
-            this.1
-                .key_value_spans("data_nodes", this.0.number_of_data_nodes),
-            this.1
-                .key_value_spans("active_shards", this.0.active_shards),
-            this.1
-                .key_value_spans("active_primary_shards", this.0.active_primary_shards),
-            this.1
-                .key_value_spans("initializing_shards", this.0.initializing_shards),
-            this.1.key_value_spans(
+impl ClusterData {
+    fn mark_ok(&mut self, resource: ElasticsearchResourceKind) {
+        self.statuses.insert(resource, FetchStatus::Ok);
+        self.last_ok.insert(resource, Instant::now());
+    }
+}
+
+/// Result of the latest fetch of the resource.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) enum FetchStatus {
+    #[default]
+    NotRequested,
+    InFlight,
+    Ok,
+    Err(String),
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct GlobalSearchResult {
+    pub(super) pattern: String,
+    /// (cluster name, index) sorted by cluster name then index name.
+    pub(super) indices: Vec<(String, CatIndex)>,
+    pub(super) failed: Vec<(String, String)>,
+}
+
+impl GlobalSearchResult {
+    pub(super) fn new(
+        pattern: String,
+        indices: Vec<(String, CatIndices)>,
+        mut failed: Vec<(String, String)>,
+    ) -> Self {
+        let mut indices: Vec<(String, CatIndex)> = indices
+            .into_iter()
+            .flat_map(|(cluster, indices)| {
+                indices
+                    .into_iter()
+                    .map(move |index| (cluster.clone(), index))
+            })
+            .collect();
+        indices.sort_unstable_by(|(c1, i1), (c2, i2)| (c1, &i1.index).cmp(&(c2, &i2.index)));
+        failed.sort_unstable();
+
+        Self {
+            pattern,
+            indices,
+            failed,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct RawResult {
+    pub(super) cluster_name: String,
+    pub(super) path: String,
+    pub(super) status: u16,
+    body: String,
+    /// Parsed body to format on render, `None` if the body is not json.
+    json: Option<serde_json::Value>,
+}
+
+impl RawResult {
+    pub(super) fn new(cluster_name: String, path: String, response: RawResponse) -> Self {
+        let json = serde_json::from_str::<serde_json::Value>(&response.body).ok();
+
+        Self {
+            cluster_name,
+            path,
+            status: response.status,
+            body: response.body,
+            json,
+        }
+    }
+
+    pub(super) fn body(&self, compact: bool) -> String {
+        match self.json.as_ref() {
+            Some(json) => format_json(json, compact),
+            None => self.body.clone(),
+        }
+    }
+}
+
+pub(super) struct ClusterHealthFormatter<'a>(
+    pub(super) &'a ClusterHealth,
+    pub(super) &'a Styled,
+    /// Thousands separator of the counts.
+    pub(super) &'a str,
+);
+
+impl<'a> ClusterHealthFormatter<'a> {
+    /// Key value lines of the health, one field per line.
+    pub(super) fn spans(self) -> Vec<Spans<'a>> {
+        let count = |n: i64| format_count(n, self.2);
+        vec![
+            self.1.key_value_spans("cluster_name", &self.0.cluster_name),
+            self.1.key_value_spans("status", &self.0.status),
+            self.1
+                .key_value_spans("nodes", count(self.0.number_of_nodes)),
+            self.1
+                .key_value_spans("data_nodes", count(self.0.number_of_data_nodes)),
+            self.1
+                .key_value_spans("active_shards", count(self.0.active_shards)),
+            self.1
+                .key_value_spans("active_primary_shards", count(self.0.active_primary_shards)),
+            self.1
+                .key_value_spans("initializing_shards", count(self.0.initializing_shards)),
+            self.1.key_value_spans_with_style(
+                "unassigned_shards",
+                count(self.0.unassigned_shards),
+                if self.0.unassigned_shards > 0 {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default().fg(Color::Yellow)
+                },
+            ),
+            self.1.key_value_spans(
                 "delayed_unassigned_shards",
-                this.0.delayed_unassigned_shards,
+                count(self.0.delayed_unassigned_shards),
             ),
-            this.1
-                .key_value_spans("relocating_shards", this.0.relocating_shards),
-            this.1
-                .key_value_spans("in_flight_fetch", this.0.number_of_in_flight_fetch),
-            this.1
-                .key_value_spans("pending_tasks", this.0.number_of_pending_tasks),
-            this.1.key_value_spans(
+            self.1
+                .key_value_spans("relocating_shards", count(self.0.relocating_shards)),
+            self.1
+                .key_value_spans("in_flight_fetch", count(self.0.number_of_in_flight_fetch)),
+            self.1
+                .key_value_spans("pending_tasks", count(self.0.number_of_pending_tasks)),
+            self.1.key_value_spans(
                 "task_max_waiting_in_queue_millis",
-                this.0.task_max_waiting_in_queue_millis,
+                self.0.task_max_waiting_in_queue_millis,
             ), // TODO: humanize duration
-        ];
+            // the health request itself timed out, so the other values may be stale.
+            if self.0.timed_out {
+                self.1.key_value_spans_with_style(
+                    "timed_out",
+                    "⚠ true",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )
+            } else {
+                self.1.key_value_spans("timed_out", false)
+            },
+        ]
+    }
+}
 
-        Text::from(v)
+/// Copies counted by the docs and store size of the index table.
+///
+/// `_cat/indices` counts the docs of the primaries only but the store size of all copies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum SizeScope {
+    /// Primaries and replicas.
+    #[default]
+    Total,
+    Primaries,
+}
+
+impl SizeScope {
+    pub(super) fn toggle(self) -> Self {
+        match self {
+            SizeScope::Total => SizeScope::Primaries,
+            SizeScope::Primaries => SizeScope::Total,
+        }
+    }
+
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            SizeScope::Total => "total",
+            SizeScope::Primaries => "pri",
+        }
+    }
+
+    /// Docs of the replicas are assumed to be the same as the primaries.
+    pub(super) fn docs_count(self, index: &CatIndex) -> String {
+        match self {
+            SizeScope::Primaries => index.docs_count.clone(),
+            SizeScope::Total => match (index.docs_count.parse::<u64>(), index.rep.parse::<u64>()) {
+                (Ok(docs), Ok(rep)) => docs.saturating_mul(rep + 1).to_string(),
+                _ => index.docs_count.clone(),
+            },
+        }
+    }
+
+    /// Bytes, closed indices have no primary store size.
+    pub(super) fn store_size(self, index: &CatIndex) -> &str {
+        match self {
+            SizeScope::Total => index.store_size.as_str(),
+            SizeScope::Primaries => index.pri_store_size.as_deref().unwrap_or_default(),
+        }
+    }
+}
+
+/// Prefix shared by the indices of a time series, like "logs" of "logs-2024.01.01". The first
+/// group captured by the pattern if given, otherwise the name before the last `-`.
+pub(super) fn index_prefix<'a>(name: &'a str, pattern: Option<&Regex>) -> Option<&'a str> {
+    match pattern {
+        Some(pattern) => pattern
+            .captures(name)
+            .and_then(|captures| captures.get(1))
+            .map(|prefix| prefix.as_str()),
+        None => name.rsplit_once('-').map(|(prefix, _)| prefix),
+    }
+}
+
+/// One row of the indices sharing the prefix, with the worst health and the summed counts.
+/// The shard counts are not summed to keep them out of the index warnings.
+pub(super) fn summarize_indices(prefix: &str, indices: &[&CatIndex], scope: SizeScope) -> CatIndex {
+    fn sum<'a>(values: impl Iterator<Item = &'a str>) -> String {
+        // closed indices have no counts.
+        values
+            .filter_map(|value| value.parse::<u64>().ok())
+            .sum::<u64>()
+            .to_string()
+    }
+
+    let health = indices
+        .iter()
+        .map(|index| index.health.as_str())
+        .min_by_key(|health| health_order(Some(health)))
+        .unwrap_or("-");
+    // replicas are already counted, the total scope keeps it as is without the replica count.
+    let docs_count: Vec<String> = indices
+        .iter()
+        .map(|index| scope.docs_count(index))
+        .collect();
+
+    CatIndex {
+        docs_count: sum(docs_count.iter().map(String::as_str)),
+        docs_deleted: sum(indices.iter().map(|index| index.docs_deleted.as_str())),
+        health: health.to_owned(),
+        index: format!("{prefix}* ({})", indices.len()),
+        pri: "-".to_owned(),
+        pri_store_size: Some(sum(indices
+            .iter()
+            .filter_map(|index| index.pri_store_size.as_deref()))),
+        rep: "-".to_owned(),
+        status: "-".to_owned(),
+        store_size: sum(indices.iter().map(|index| index.store_size.as_str())),
+        uuid: None,
+    }
+}
+
+/// Totals of the indices shown in the index table.
+#[derive(Debug, Default)]
+pub(super) struct IndexSummary {
+    indices: usize,
+    docs: u64,
+    store_size: u64,
+    green: usize,
+    yellow: usize,
+    red: usize,
+}
+
+impl IndexSummary {
+    pub(super) fn new(indices: &[&CatIndex], scope: SizeScope) -> Self {
+        indices
+            .iter()
+            .fold(IndexSummary::default(), |mut summary, index| {
+                summary.indices += 1;
+                summary.docs += scope.docs_count(index).parse::<u64>().unwrap_or(0);
+                summary.store_size += scope.store_size(index).parse::<u64>().unwrap_or(0);
+                match index.health.as_str() {
+                    "green" => summary.green += 1,
+                    "yellow" => summary.yellow += 1,
+                    "red" => summary.red += 1,
+                    _ => (),
+                }
+                summary
+            })
+    }
+
+    /// One line of the totals followed by counts by health.
+    pub(super) fn spans<'a>(
+        &self,
+        style: &Styled,
+        units: ByteUnits,
+        thousands_separator: &str,
+        scope: SizeScope,
+    ) -> Spans<'a> {
+        let mut spans = Vec::new();
+        for kv in [
+            style.key_value_spans("indices", self.indices),
+            style.key_value_spans(
+                format!("docs ({})", scope.label()),
+                format_count(self.docs, thousands_separator),
+            ),
+            style.key_value_spans(
+                format!("store_size ({})", scope.label()),
+                humanize_bytes(self.store_size, units),
+            ),
+        ] {
+            spans.extend(kv.0);
+            spans.push(Span::raw("  "));
+        }
+        for (health, count) in [
+            ("green", self.green),
+            ("yellow", self.yellow),
+            ("red", self.red),
+        ] {
+            spans.extend(
+                style
+                    .key_value_spans_with_style(
+                        health,
+                        count,
+                        Style::default().fg(health_color(health)),
+                    )
+                    .0,
+            );
+            spans.push(Span::raw(" "));
+        }
+        Spans::from(spans)
+    }
+}
+
+/// Sort indices in the order of the index table.
+pub(super) fn sort_indices(indices: &mut [&CatIndex], sort: IndexSort) {
+    match sort {
+        IndexSort::Name => indices.sort_unstable_by_key(|index| &index.index),
+        IndexSort::Health => indices.sort_unstable_by_key(|index| {
+            (health_order(Some(index.health.as_str())), &index.index)
+        }),
+        IndexSort::StoreSize => indices.sort_unstable_by_key(|index| {
+            (
+                cmp::Reverse(index.store_size.parse::<u64>().unwrap_or(0)),
+                &index.index,
+            )
+        }),
+    }
+}
+
+/// Group the aliases by name in the order of their first appearance, so that the sort is kept.
+pub(super) fn group_aliases<'a>(aliases: &[&'a CatAlias]) -> Vec<(&'a str, Vec<&'a CatAlias>)> {
+    let mut groups: Vec<(&str, Vec<&CatAlias>)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for alias in aliases {
+        match positions.get(alias.alias.as_str()) {
+            Some(&i) => groups[i].1.push(alias),
+            None => {
+                positions.insert(alias.alias.as_str(), groups.len());
+                groups.push((alias.alias.as_str(), vec![alias]));
+            }
+        }
+    }
+    groups
+}
+
+fn is_hidden(index: &CatIndex) -> bool {
+    index.index.starts_with('.')
+}
+
+/// Sort key to bring unhealthy first, unknown health comes last.
+pub(super) fn health_order(health: Option<&str>) -> u8 {
+    match health {
+        Some("red") => 0,
+        Some("yellow") => 1,
+        Some("green") => 2,
+        _ => 3,
     }
 }
 
@@ -118,8 +975,234 @@ pub(super) fn health_color(health: &str) -> Color {
     }
 }
 
-pub(super) fn humanize_str_bytes(s: &str) -> String {
+pub(super) fn snapshot_state_color(state: &str) -> Color {
+    match state {
+        "SUCCESS" => Color::Green,
+        "IN_PROGRESS" => Color::Yellow,
+        "FAILED" | "PARTIAL" => Color::Red,
+        _ => Color::White,
+    }
+}
+
+pub(super) fn humanize_str_bytes(s: &str, units: ByteUnits) -> String {
     s.parse::<u64>()
-        .map(|n| humansize::format_size(n, humansize::BINARY))
+        .map(|n| humanize_bytes(n, units))
         .unwrap_or_else(|_| "unknown".to_owned())
 }
+
+/// Store size in the display, the humanized one if it is not a number.
+/// Closed indices have no size, which is shown as "-".
+pub(super) fn format_str_bytes(s: &str, units: ByteUnits, display: ByteDisplay) -> String {
+    if s.is_empty() {
+        return "-".to_owned();
+    }
+    match (display, s.parse::<u64>()) {
+        (ByteDisplay::Both, Ok(n)) => format!("{} ({n})", humanize_bytes(n, units)),
+        (ByteDisplay::Raw, Ok(n)) => n.to_string(),
+        _ => humanize_str_bytes(s, units),
+    }
+}
+
+fn humanize_bytes(n: u64, units: ByteUnits) -> String {
+    let options = match units {
+        ByteUnits::Binary => humansize::BINARY,
+        ByteUnits::Decimal => humansize::DECIMAL,
+    };
+    humansize::format_size(n, options)
+}
+
+/// Group the digits by thousands with the separator, like "1,234,567".
+pub(super) fn format_count(n: impl Into<i128>, separator: &str) -> String {
+    let n = n.into();
+    let digits = n.unsigned_abs().to_string();
+    let mut grouped = String::with_capacity(digits.len() * (1 + separator.len()));
+    if n < 0 {
+        grouped.push('-');
+    }
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push_str(separator);
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// Count returned as a string by the cat api, kept as is if it is not a number.
+/// Closed indices have no count, which is shown as "-".
+pub(super) fn format_str_count(s: &str, separator: &str) -> String {
+    if s.is_empty() {
+        return "-".to_owned();
+    }
+    s.parse::<i64>()
+        .map(|n| format_count(n, separator))
+        .unwrap_or_else(|_| s.to_owned())
+}
+
+/// Coarse duration like "1h 5m" or "42s".
+pub(super) fn humanize_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0 => format!("{}ms", duration.as_millis()),
+        1..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Format of the timestamps, the zone abbreviation tells which time zone they are in.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
+
+/// Renders the timestamps in the time zone, or as their age like "3h 5m ago".
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TimestampFormat {
+    pub(super) tz: Tz,
+    pub(super) relative: bool,
+}
+
+impl TimestampFormat {
+    /// Time like "2023-01-02 09:30:00 JST" or "3h 5m ago".
+    pub(super) fn system_time(self, time: SystemTime) -> String {
+        if self.relative {
+            return match SystemTime::now().duration_since(time) {
+                Ok(age) => format!("{} ago", humanize_duration(age)),
+                Err(err) => format!("in {}", humanize_duration(err.duration())),
+            };
+        }
+        DateTime::<Utc>::from(time)
+            .with_timezone(&self.tz)
+            .format(TIMESTAMP_FORMAT)
+            .to_string()
+    }
+
+    /// Epoch milliseconds, with the age like "2023-01-02 09:30:00 JST (3h 5m ago)" if absolute,
+    /// as is if it is not a timestamp.
+    pub(super) fn epoch_millis(self, millis: &str) -> String {
+        let created = match millis.parse::<u64>() {
+            Ok(millis) => UNIX_EPOCH + Duration::from_millis(millis),
+            Err(_) => return millis.to_owned(),
+        };
+        let timestamp = self.system_time(created);
+        match SystemTime::now().duration_since(created) {
+            Ok(age) if !self.relative => format!("{timestamp} ({} ago)", humanize_duration(age)),
+            _ => timestamp,
+        }
+    }
+
+    /// RFC 3339 timestamp, as is if it can not be parsed.
+    pub(super) fn rfc3339(self, timestamp: &str) -> String {
+        match DateTime::parse_from_rfc3339(timestamp) {
+            Ok(timestamp) => self.system_time(timestamp.into()),
+            Err(_) => timestamp.to_owned(),
+        }
+    }
+}
+
+/// Number of fields in the mappings, including the fields of the objects.
+pub(super) fn count_mapping_fields(mappings: &IndexMappings) -> usize {
+    fn count<'a>(fields: impl Iterator<Item = &'a serde_json::Value>) -> usize {
+        fields
+            .map(
+                |field| match field.get("properties").and_then(|p| p.as_object()) {
+                    Some(properties) => 1 + count(properties.values()),
+                    None => 1,
+                },
+            )
+            .sum()
+    }
+    count(mappings.properties.values())
+}
+
+/// Pretty printed json, or single line if compact.
+pub(super) fn format_json(value: &serde_json::Value, compact: bool) -> String {
+    if compact {
+        return value.to_string();
+    }
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// Flat setting value without quotes, arrays are kept as json.
+pub(super) fn setting_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Keep the first `max_lines` lines, marking the rest as omitted.
+pub(super) fn truncate_lines(s: &str, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = s.lines().take(max_lines).map(str::to_owned).collect();
+    if s.lines().nth(max_lines).is_some() {
+        lines.push("…".to_owned());
+    }
+    lines
+}
+
+/// Order ILM phases as they are executed.
+pub(super) fn ilm_phase_order(phase: &str) -> usize {
+    match phase {
+        "hot" => 0,
+        "warm" => 1,
+        "cold" => 2,
+        "frozen" => 3,
+        "delete" => 4,
+        _ => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn humanize_bytes_by_units() {
+        assert_eq!(humanize_str_bytes("1048576", ByteUnits::Binary), "1 MiB");
+        assert_eq!(humanize_str_bytes("1048576", ByteUnits::Decimal), "1.05 MB");
+        assert_eq!(humanize_str_bytes("-", ByteUnits::Binary), "unknown");
+    }
+
+    #[test]
+    fn format_count_groups_thousands() {
+        assert_eq!(format_count(0, ","), "0");
+        assert_eq!(format_count(999, ","), "999");
+        assert_eq!(format_count(1000, ","), "1,000");
+        assert_eq!(format_count(1234567, ","), "1,234,567");
+        assert_eq!(format_count(-1234567, ","), "-1,234,567");
+        assert_eq!(format_count(i64::MIN, ","), "-9,223,372,036,854,775,808");
+    }
+
+    #[test]
+    fn format_count_with_separator() {
+        assert_eq!(format_count(1234567, "_"), "1_234_567");
+        assert_eq!(format_count(1234567, ""), "1234567");
+        assert_eq!(format_count(1234567, " "), "1 234 567");
+    }
+
+    #[test]
+    fn format_str_count_keeps_non_numbers() {
+        assert_eq!(format_str_count("1234", ","), "1,234");
+        // closed indices have no count.
+        assert_eq!(format_str_count("", ","), "-");
+        assert_eq!(format_str_count("-", ","), "-");
+    }
+
+    #[test]
+    fn format_missing_bytes() {
+        assert_eq!(
+            format_str_bytes("", ByteUnits::Binary, ByteDisplay::Both),
+            "-"
+        );
+    }
+
+    #[test]
+    fn format_bytes_by_display() {
+        assert_eq!(
+            format_str_bytes("1048576", ByteUnits::Decimal, ByteDisplay::Both),
+            "1.05 MB (1048576)"
+        );
+        assert_eq!(
+            format_str_bytes("1048576", ByteUnits::Binary, ByteDisplay::Raw),
+            "1048576"
+        );
+    }
+}
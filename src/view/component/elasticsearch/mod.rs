@@ -1,36 +1,56 @@
 use std::{
     cmp,
+    collections::{BTreeMap, HashMap},
     fmt::{self, Display},
+    time::Duration,
 };
 
+use chrono_tz::Tz;
 use data::Data;
+use itertools::Itertools;
+use regex::Regex;
 use tui::{
+    buffer::Buffer,
     layout::{
         Alignment, Constraint,
         Direction::{Horizontal, Vertical},
-        Layout,
+        Layout, Rect,
     },
     style::{Color, Modifier, Style},
-    text::{Span, Text},
-    widgets::{Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState},
+    text::{Span, Spans, Text},
+    widgets::{
+        Cell, Clear, List, ListItem, ListState, Paragraph, Row, Sparkline, Table, TableState,
+        Widget, Wrap,
+    },
 };
+use url::Url;
 use ElasticsearchComponentKind::*;
 use ElasticsearchResourceKind::*;
 
 use crate::{
-    client::elasticsearch::response::{CatAlias, CatIndex},
+    client::elasticsearch::response::{CatAlias, CatIndex, ClusterHealth, IndexTemplateEntry},
     event::api::{
         elasticsearch::{ElasticsearchRequestEvent, ElasticsearchResponseEvent},
         RequestEvent,
     },
     view::{
         component::{
-            elasticsearch::data::{health_color, humanize_str_bytes, ClusterHealthFormatter},
+            elasticsearch::data::{
+                count_mapping_fields, format_count, format_json, format_str_bytes,
+                format_str_count, group_aliases, health_color, health_order, humanize_duration,
+                humanize_str_bytes, ilm_phase_order, index_prefix, setting_value,
+                snapshot_state_color, sort_indices, summarize_indices, truncate_lines,
+                ClusterHealthFormatter, FetchStatus, GlobalSearchResult, IndexChange, IndexSummary,
+                RawResult, SizeScope, TimestampFormat,
+            },
+            filter::{fuzzy_score, FilterMode, TableFilter},
+            redact::Redactor,
             StringUtil,
         },
-        ApplyNavigate, Navigate, ViewContext,
+        style::{parse_color, Styled},
+        ApplyNavigate, ColumnAdjust, Navigate, TextEdit, ViewContext,
     },
-    ElasticsearchConfig,
+    ByteDisplay, ByteUnits, ElasticsearchConfig, IndexColumn, IndexSort, SessionState,
 };
 
 mod data;
@@ -39,46 +59,272 @@ mod data;
 pub(crate) enum ElasticsearchComponentKind {
     ClusterList,
     ResourceList,
+    DashboardTable,
+    NodeTable,
     AliasTable,
     IndexTable,
+    TemplateTable,
+    IlmPolicyList,
+    RepositoryList,
+    SnapshotTable,
+    IndexDetail,
+    GlobalSearch,
+    SettingTable,
+    AllocationDetail,
+    TaskTable,
+    /// Type to jump to an index of the index table.
+    IndexPalette,
+    /// Type a path to `GET` from the selected cluster.
+    RawGet,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl ElasticsearchComponentKind {
+    pub(crate) fn accepts_text(&self) -> bool {
+        // the setting table is filtered by typing.
+        matches!(self, GlobalSearch | SettingTable | IndexPalette | RawGet)
+    }
+
+    /// Whether the component is placed on the left panel.
+    pub(crate) fn is_left_panel(&self) -> bool {
+        matches!(self, ClusterList | ResourceList)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum ElasticsearchResourceKind {
     Cluster,
+    /// Health of all clusters.
+    Dashboard,
+    Node,
     Index,
     Alias,
+    Template,
+    Ilm,
+    Snapshot,
+    Settings,
+    /// Running tasks like reindex.
+    Task,
+}
+
+/// Order of the cluster list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ClusterSort {
+    /// As written in config.
+    #[default]
+    Config,
+    Name,
+    /// Red first to surface problems.
+    Health,
+}
+
+impl ClusterSort {
+    fn next(self) -> Self {
+        match self {
+            ClusterSort::Config => ClusterSort::Name,
+            ClusterSort::Name => ClusterSort::Health,
+            ClusterSort::Health => ClusterSort::Config,
+        }
+    }
+}
+
+/// Row of the index table.
+enum IndexRow<'a> {
+    Index(&'a CatIndex),
+    /// Indices sharing the prefix if the indices are grouped.
+    Group {
+        prefix: &'a str,
+        members: Vec<&'a CatIndex>,
+    },
+    /// Index of the expanded group.
+    Member(&'a CatIndex),
+}
+
+impl<'a> IndexRow<'a> {
+    /// `None` for the group which is not an index.
+    fn index(&self) -> Option<&'a CatIndex> {
+        match self {
+            IndexRow::Index(index) | IndexRow::Member(index) => Some(index),
+            IndexRow::Group { .. } => None,
+        }
+    }
+}
+
+/// Row of the alias table.
+enum AliasRow<'a> {
+    Alias(&'a CatAlias),
+    /// Alias pointing to many indices if the aliases are grouped.
+    Group {
+        alias: &'a str,
+        members: Vec<&'a CatAlias>,
+    },
+    /// Index of the expanded group.
+    Member(&'a CatAlias),
+}
+
+impl<'a> AliasRow<'a> {
+    fn alias(&self) -> &'a str {
+        match self {
+            AliasRow::Alias(alias) | AliasRow::Member(alias) => alias.alias.as_str(),
+            AliasRow::Group { alias, .. } => alias,
+        }
+    }
+}
+
+/// Order of the alias table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum AliasSort {
+    #[default]
+    Alias,
+    /// Aliases pointing to the same index come together.
+    Index,
+    /// Write indices first, by alias name within the same kind.
+    WriteFirst,
+}
+
+impl AliasSort {
+    fn next(self) -> Self {
+        match self {
+            AliasSort::Alias => AliasSort::Index,
+            AliasSort::Index => AliasSort::WriteFirst,
+            AliasSort::WriteFirst => AliasSort::Alias,
+        }
+    }
 }
 
 impl Display for ElasticsearchResourceKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             ElasticsearchResourceKind::Cluster => "cluster",
+            ElasticsearchResourceKind::Dashboard => "dashboard",
+            ElasticsearchResourceKind::Node => "node",
             ElasticsearchResourceKind::Index => "index",
             ElasticsearchResourceKind::Alias => "alias",
+            ElasticsearchResourceKind::Template => "template",
+            ElasticsearchResourceKind::Ilm => "ilm",
+            ElasticsearchResourceKind::Snapshot => "snapshot",
+            ElasticsearchResourceKind::Settings => "settings",
+            ElasticsearchResourceKind::Task => "task",
         };
         f.write_str(s)
     }
 }
 
+/// Default `index.max_result_window`, `from + size` beyond it is rejected.
+const MAX_RESULT_WINDOW: i64 = 10_000;
+
+/// Bounds of an index table column width adjusted by the user.
+const MIN_COLUMN_WIDTH: i16 = 3;
+const MAX_COLUMN_WIDTH: i16 = 200;
+
 pub(crate) struct ElasticsearchComponent {
     configs: Vec<ElasticsearchConfig>,
     resources: &'static [ElasticsearchResourceKind],
+    /// Named filters of the index table from config.
+    index_filter_presets: Vec<(String, TableFilter)>,
+    /// Colors of the cluster names to tell environments apart.
+    label_colors: HashMap<String, Color>,
+    /// Clusters whose client failed to build, greyed out in the cluster list.
+    build_failures: HashMap<String, String>,
+    /// Units of the humanized store sizes.
+    byte_units: ByteUnits,
+    /// Separator of thousands in counts.
+    thousands_separator: String,
+    /// Columns of the index table in order.
+    index_columns: Vec<IndexColumn>,
+    /// Show the primary and replica shards in one column.
+    merge_shard_columns: bool,
+    /// Collect the clusters turned red to alert them.
+    alert_on_red: bool,
+    /// Warm the other resources of the cluster on init.
+    prefetch_on_startup: bool,
+    /// Fetch the selected resource on focus instead of showing the cached one.
+    refresh_on_focus: bool,
+    /// Show the aliases of many indices in one row.
+    group_aliases: bool,
+    /// Captures the prefix of the grouped indices, `None` splits the name on the last `-`.
+    index_group_pattern: Option<Regex>,
+    /// Time zone the timestamps are shown in.
+    timezone: Tz,
+    /// Order of the index table from config, restored on reset.
+    default_index_sort: IndexSort,
     state: State,
     data: Data,
 }
 
 struct State {
     focused: Option<ElasticsearchComponentKind>,
+    entered: Option<ElasticsearchComponentKind>,
     cluster_list_state: ListState,
     resource_list_state: ListState,
+    dashboard_table_state: TableState,
+    node_table_state: TableState,
     index_table_state: TableState,
     alias_table_state: TableState,
+    template_table_state: TableState,
+    ilm_policy_list_state: ListState,
+    repository_list_state: ListState,
+    snapshot_table_state: TableState,
+    index_detail_scroll: u16,
+    allocation_detail_scroll: u16,
+    global_search_query: String,
+    global_search_table_state: TableState,
+    index_palette_query: String,
+    index_palette_list_state: ListState,
+    raw_get_path: String,
+    raw_response_scroll: u16,
+    left_panel_collapsed: bool,
+    setting_table_state: TableState,
+    task_table_state: TableState,
+    setting_filter: TableFilter,
+    /// Position of the active index filter preset, `None` shows all indices.
+    index_filter_preset: Option<usize>,
+    /// Show the second detail line for each row.
+    wide_rows: bool,
+    /// Hide cluster and index names for screenshots.
+    redactor: Redactor,
+    /// Whether the docs and store size of the index table include replicas.
+    size_scope: SizeScope,
+    byte_display: ByteDisplay,
+    /// Render the json of the detail panes in a single line instead of pretty printed.
+    compact_json: bool,
+    /// Render the timestamps as their age instead of the time.
+    relative_timestamps: bool,
+    /// Show the outcome per cluster of the latest request to all clusters.
+    show_fan_out: bool,
+    /// Show the health transitions of the clusters in this session.
+    show_health_log: bool,
+    /// Position of the index table column whose width is adjusted.
+    index_active_column: usize,
+    /// Widths added to the defaults of the index table columns, negative to narrow.
+    index_column_widths: HashMap<IndexColumn, i16>,
+    /// Default widths of the index table columns last rendered, to bound the adjusted widths.
+    index_column_default_widths: HashMap<IndexColumn, u16>,
+    /// Width of the index table last rendered, narrow tables hide the lower priority columns.
+    index_table_width: Option<u16>,
+    cluster_sort: ClusterSort,
+    alias_sort: AliasSort,
+    index_sort: IndexSort,
+    /// Include dot-prefixed system indices in the index table.
+    show_hidden_indices: bool,
+    /// Poll the cluster health to watch it change.
+    follow: bool,
+    /// Clusters turned red and not alerted yet.
+    turned_red: Vec<String>,
+    /// Index whose row is expanded in the index table, one at a time.
+    expanded_index: Option<String>,
+    /// Grouped alias whose indices are listed in the alias table.
+    expanded_alias: Option<String>,
+    /// Collapse the indices sharing a prefix into one row.
+    group_indices: bool,
+    /// Prefix of the index group whose indices are listed in the index table.
+    expanded_index_group: Option<String>,
 }
 
 impl ElasticsearchComponent {
     pub(crate) fn new(configs: Vec<ElasticsearchConfig>) -> Self {
-        static RESOURCES: &[ElasticsearchResourceKind] = &[Cluster, Index, Alias];
+        static RESOURCES: &[ElasticsearchResourceKind] = &[
+            Cluster, Dashboard, Node, Index, Alias, Template, Ilm, Snapshot, Settings, Task,
+        ];
 
         let mut cluster_list_state = ListState::default();
         cluster_list_state.select(Some(0));
@@ -86,30 +332,310 @@ impl ElasticsearchComponent {
         let mut resource_list_state = ListState::default();
         resource_list_state.select(Some(0));
 
+        let mut dashboard_table_state = TableState::default();
+        dashboard_table_state.select(Some(0));
+
+        let mut node_table_state = TableState::default();
+        node_table_state.select(Some(0));
+
         let mut index_table_state = TableState::default();
         index_table_state.select(Some(0));
 
         let mut alias_table_state = TableState::default();
         alias_table_state.select(Some(0));
 
+        let mut template_table_state = TableState::default();
+        template_table_state.select(Some(0));
+
+        let mut global_search_table_state = TableState::default();
+        global_search_table_state.select(Some(0));
+
+        let mut setting_table_state = TableState::default();
+        setting_table_state.select(Some(0));
+
+        let mut task_table_state = TableState::default();
+        task_table_state.select(Some(0));
+
+        let mut ilm_policy_list_state = ListState::default();
+        ilm_policy_list_state.select(Some(0));
+
+        let mut repository_list_state = ListState::default();
+        repository_list_state.select(Some(0));
+
+        let mut snapshot_table_state = TableState::default();
+        snapshot_table_state.select(Some(0));
+
+        let redactor = Redactor::new(configs.iter().map(|c| c.name.clone()).collect());
+
+        let label_colors = configs
+            .iter()
+            .filter_map(|c| {
+                let color = c.label_color.as_deref()?;
+                match parse_color(color) {
+                    Some(color) => Some((c.name.clone(), color)),
+                    None => {
+                        tracing::warn!(cluster_name = c.name, color, "Unknown label color");
+                        None
+                    }
+                }
+            })
+            .collect();
+
         Self {
             configs,
             resources: RESOURCES,
+            index_filter_presets: Vec::new(),
+            label_colors,
+            build_failures: HashMap::new(),
+            byte_units: ByteUnits::default(),
+            thousands_separator: String::new(),
+            index_columns: IndexColumn::ALL.to_vec(),
+            merge_shard_columns: false,
+            alert_on_red: false,
+            prefetch_on_startup: false,
+            refresh_on_focus: false,
+            group_aliases: false,
+            index_group_pattern: None,
+            timezone: Tz::UTC,
+            default_index_sort: IndexSort::default(),
             state: State {
                 focused: None,
+                entered: None,
                 cluster_list_state,
                 resource_list_state,
+                dashboard_table_state,
+                node_table_state,
                 index_table_state,
                 alias_table_state,
+                template_table_state,
+                ilm_policy_list_state,
+                repository_list_state,
+                snapshot_table_state,
+                index_detail_scroll: 0,
+                allocation_detail_scroll: 0,
+                global_search_query: String::new(),
+                global_search_table_state,
+                index_palette_query: String::new(),
+                index_palette_list_state: ListState::default(),
+                raw_get_path: String::new(),
+                raw_response_scroll: 0,
+                left_panel_collapsed: false,
+                setting_table_state,
+                task_table_state,
+                setting_filter: TableFilter::default(),
+                index_filter_preset: None,
+                wide_rows: false,
+                redactor,
+                size_scope: SizeScope::default(),
+                byte_display: ByteDisplay::default(),
+                compact_json: false,
+                relative_timestamps: false,
+                show_fan_out: false,
+                show_health_log: false,
+                index_active_column: 0,
+                index_column_widths: HashMap::new(),
+                index_column_default_widths: HashMap::new(),
+                index_table_width: None,
+                cluster_sort: ClusterSort::default(),
+                alias_sort: AliasSort::default(),
+                index_sort: IndexSort::default(),
+                show_hidden_indices: false,
+                follow: false,
+                turned_red: Vec::new(),
+                expanded_index: None,
+                expanded_alias: None,
+                group_indices: false,
+                expanded_index_group: None,
             },
             data: Data::new(),
         }
     }
 
+    /// Select the cluster on startup instead of the first one.
+    pub(crate) fn with_default_cluster(mut self, cluster_name: Option<&str>) -> Self {
+        if let Some(cluster_name) = cluster_name {
+            let position = self.cluster_names().position(|name| name == cluster_name);
+            match position {
+                Some(idx) => self.state.cluster_list_state.select(Some(idx)),
+                None => tracing::warn!(cluster_name, "Default cluster not found in config"),
+            }
+        }
+        self
+    }
+
+    /// Select the resource on startup instead of the cluster.
+    pub(crate) fn with_default_resource(mut self, resource: Option<&str>) -> Self {
+        if let Some(resource) = resource {
+            match self
+                .resources
+                .iter()
+                .position(|r| r.to_string() == resource)
+            {
+                Some(idx) => self.state.resource_list_state.select(Some(idx)),
+                None => tracing::warn!(resource, "Unknown default resource"),
+            }
+        }
+        self
+    }
+
+    pub(crate) fn with_index_filters(mut self, filters: BTreeMap<String, String>) -> Self {
+        self.index_filter_presets = filters
+            .into_iter()
+            .map(|(name, pattern)| (name, TableFilter::new(pattern).with_mode(FilterMode::Glob)))
+            .collect();
+        self
+    }
+
+    /// Apply the filter preset, or the pattern as a new preset, on startup.
+    /// Must be called after the presets are configured.
+    pub(crate) fn with_default_index_filter(mut self, filter: Option<String>) -> Self {
+        if let Some(filter) = filter {
+            let position = match self
+                .index_filter_presets
+                .iter()
+                .position(|(name, _)| *name == filter)
+            {
+                Some(position) => position,
+                None => {
+                    self.index_filter_presets.push((
+                        filter.clone(),
+                        TableFilter::new(filter).with_mode(FilterMode::Glob),
+                    ));
+                    self.index_filter_presets.len() - 1
+                }
+            };
+            self.state.index_filter_preset = Some(position);
+        }
+        self
+    }
+
+    pub(crate) fn with_index_sort(mut self, sort: IndexSort) -> Self {
+        self.default_index_sort = sort;
+        self.state.index_sort = sort;
+        self
+    }
+
+    pub(crate) fn with_show_hidden_indices(mut self, show: bool) -> Self {
+        self.state.show_hidden_indices = show;
+        self
+    }
+
+    pub(crate) fn with_byte_display(mut self, display: ByteDisplay) -> Self {
+        self.state.byte_display = display;
+        self
+    }
+
+    pub(crate) fn with_byte_units(mut self, units: ByteUnits) -> Self {
+        self.byte_units = units;
+        self
+    }
+
+    pub(crate) fn with_thousands_separator(mut self, separator: String) -> Self {
+        self.thousands_separator = separator;
+        self
+    }
+
+    pub(crate) fn with_index_columns(mut self, columns: Vec<IndexColumn>) -> Self {
+        self.index_columns = columns;
+        self
+    }
+
+    pub(crate) fn with_merge_shard_columns(mut self, merge: bool) -> Self {
+        self.merge_shard_columns = merge;
+        self
+    }
+
+    pub(crate) fn with_alert_on_red(mut self, alert: bool) -> Self {
+        self.alert_on_red = alert;
+        self
+    }
+
+    pub(crate) fn cluster_count(&self) -> usize {
+        self.configs.len()
+    }
+
+    pub(crate) fn with_build_failures(mut self, failures: HashMap<String, String>) -> Self {
+        self.build_failures = failures;
+        self
+    }
+
+    /// Fall back to UTC if the time zone is not a valid IANA name.
+    pub(crate) fn with_timezone(mut self, timezone: Option<&str>) -> Self {
+        if let Some(timezone) = timezone {
+            match timezone.parse::<Tz>() {
+                Ok(tz) => self.timezone = tz,
+                Err(_) => tracing::warn!(timezone, "Unknown time zone, fall back to UTC"),
+            }
+        }
+        self
+    }
+
+    pub(crate) fn with_refresh_on_focus(mut self, refresh: bool) -> Self {
+        self.refresh_on_focus = refresh;
+        self
+    }
+
+    pub(crate) fn with_group_aliases(mut self, group: bool) -> Self {
+        self.group_aliases = group;
+        self
+    }
+
+    pub(crate) fn with_group_indices(mut self, group: bool) -> Self {
+        self.state.group_indices = group;
+        self
+    }
+
+    /// Invalid pattern is ignored to split the names on the last `-`.
+    pub(crate) fn with_index_group_pattern(mut self, pattern: Option<&str>) -> Self {
+        self.index_group_pattern = pattern.and_then(|pattern| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(err) => {
+                tracing::warn!(pattern, "Invalid index group pattern {err}");
+                None
+            }
+        });
+        self
+    }
+
+    pub(crate) fn with_prefetch_on_startup(mut self, prefetch: bool) -> Self {
+        self.prefetch_on_startup = prefetch;
+        self
+    }
+
     /// Initialize component data.
     pub(crate) fn init_data(&mut self) -> Option<impl Iterator<Item = RequestEvent>> {
-        self.fetch_data()
-            .map(|events| events.into_iter().map(RequestEvent::Elasticsearch))
+        let mut fetch = self.fetch_data();
+        if self.prefetch_on_startup {
+            if let Some(events) = fetch.as_mut() {
+                // skip the selected resource already fetched above.
+                let prefetch = self.prefetch_data().into_iter().filter(|event| {
+                    let resource = fetched_resource(event);
+                    !events.iter().any(|e| fetched_resource(e) == resource)
+                });
+                events.extend(prefetch.collect::<Vec<_>>());
+            }
+        }
+        self.send(fetch)
+    }
+
+    /// Resources of the selected cluster worth having before they are selected.
+    fn prefetch_data(&self) -> Vec<ElasticsearchRequestEvent> {
+        let cluster = match self.selected_cluster_name() {
+            Some(cluster) => cluster,
+            None => return Vec::new(),
+        };
+        vec![
+            ElasticsearchRequestEvent::FetchCluster {
+                cluster_name: cluster.to_owned(),
+            },
+            ElasticsearchRequestEvent::FetchIndices {
+                cluster_name: cluster.to_owned(),
+                pattern: self.index_filter().map(|filter| filter.query().to_owned()),
+            },
+            ElasticsearchRequestEvent::FetchAliases {
+                cluster_name: cluster.to_owned(),
+            },
+        ]
     }
 
     fn fetch_data(&self) -> Option<Vec<ElasticsearchRequestEvent>> {
@@ -119,89 +645,998 @@ impl ElasticsearchComponent {
                 Cluster => vec![ElasticsearchRequestEvent::FetchCluster {
                     cluster_name: cluster.to_owned(),
                 }],
+                Dashboard => vec![ElasticsearchRequestEvent::FetchAllClusterHealth],
+                Node => vec![ElasticsearchRequestEvent::FetchNodes {
+                    cluster_name: cluster.to_owned(),
+                }],
                 Index => vec![ElasticsearchRequestEvent::FetchIndices {
                     cluster_name: cluster.to_owned(),
+                    // the preset also filters the response in case of the previous pattern.
+                    pattern: self.index_filter().map(|filter| filter.query().to_owned()),
                 }],
                 Alias => vec![ElasticsearchRequestEvent::FetchAliases {
                     cluster_name: cluster.to_owned(),
                 }],
+                Template => vec![ElasticsearchRequestEvent::FetchTemplates {
+                    cluster_name: cluster.to_owned(),
+                }],
+                Ilm => vec![ElasticsearchRequestEvent::FetchIlmPolicies {
+                    cluster_name: cluster.to_owned(),
+                }],
+                Snapshot => vec![ElasticsearchRequestEvent::FetchRepositories {
+                    cluster_name: cluster.to_owned(),
+                }],
+                Settings => vec![ElasticsearchRequestEvent::FetchClusterSettings {
+                    cluster_name: cluster.to_owned(),
+                }],
+                Task => vec![ElasticsearchRequestEvent::FetchTasks {
+                    cluster_name: cluster.to_owned(),
+                }],
+            })
+    }
+
+    /// Fetch data shown in the entered component.
+    pub(crate) fn fetch_entered(&mut self) -> Option<impl Iterator<Item = RequestEvent>> {
+        let events =
+            match self.state.entered {
+                Some(IndexDetail) => self.selected_cluster_name().zip(self.selected_index()).map(
+                    |(cluster, index)| {
+                        let mut events = vec![ElasticsearchRequestEvent::FetchIndexSettings {
+                            cluster_name: cluster.to_owned(),
+                            index: index.index.clone(),
+                        }];
+                        // closed index can not be searched.
+                        if index.status != "close" {
+                            events.push(ElasticsearchRequestEvent::FetchSampleDocs {
+                                cluster_name: cluster.to_owned(),
+                                index: index.index.clone(),
+                                from: 0,
+                            });
+                        }
+                        events
+                    },
+                ),
+                Some(AllocationDetail) => self.selected_cluster_name().map(|cluster| {
+                    vec![ElasticsearchRequestEvent::FetchAllocationExplain {
+                        cluster_name: cluster.to_owned(),
+                    }]
+                }),
+                _ => None,
+            };
+        self.send(events)
+    }
+
+    /// Mark the resources as being fetched and wrap the requests.
+    fn send(
+        &mut self,
+        fetch: Option<Vec<ElasticsearchRequestEvent>>,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        for req in fetch.iter().flatten() {
+            if let Some((cluster_name, resource)) = fetched_resource(req) {
+                self.data.update_fetch_status(
+                    cluster_name.to_owned(),
+                    resource,
+                    FetchStatus::InFlight,
+                );
+            }
+        }
+        fetch.map(|events| events.into_iter().map(RequestEvent::Elasticsearch))
+    }
+
+    /// Keep the failure reason to show it in place of the resource.
+    pub(crate) fn update_api_error(&mut self, req: ElasticsearchRequestEvent, reason: String) {
+        if let Some((cluster_name, resource)) = fetched_resource(&req) {
+            self.data.update_fetch_status(
+                cluster_name.to_owned(),
+                resource,
+                FetchStatus::Err(reason),
+            );
+        }
+    }
+
+    fn fetch_snapshots(&self) -> Option<Vec<ElasticsearchRequestEvent>> {
+        self.selected_cluster_name()
+            .zip(self.selected_repository_name())
+            .map(|(cluster, repository)| {
+                vec![ElasticsearchRequestEvent::FetchSnapshots {
+                    cluster_name: cluster.to_owned(),
+                    repository: repository.to_owned(),
+                }]
             })
     }
 
-    pub(crate) fn update_api_response(&mut self, res: ElasticsearchResponseEvent) {
-        match res {
+    fn update_cluster_health(&mut self, cluster_name: String, health: ClusterHealth) {
+        let turned_red = self
+            .data
+            .update_cluster_health(cluster_name.clone(), health);
+        if turned_red && self.alert_on_red {
+            self.state.turned_red.push(cluster_name);
+        }
+    }
+
+    /// Names to display, redacted if enabled.
+    pub(crate) fn take_turned_red(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.state.turned_red)
+            .into_iter()
+            .map(|name| self.state.redactor.cluster(&name).into_owned())
+            .collect()
+    }
+
+    /// Update data by the response, and return the requests depending on it.
+    pub(crate) fn update_api_response(
+        &mut self,
+        res: ElasticsearchResponseEvent,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        let fetch = match res {
             ElasticsearchResponseEvent::ClusterHealth {
                 cluster_name,
                 response,
-            } => self.data.update_cluster_health(cluster_name, response),
+            } => {
+                // health may change the order of the cluster list.
+                let selected = self.selected_cluster_name().map(str::to_owned);
+                self.update_cluster_health(cluster_name, response);
+                self.select_cluster(selected.as_deref());
+                None
+            }
             ElasticsearchResponseEvent::Indices {
                 cluster_name,
                 response,
-            } => self.data.update_indices(cluster_name, response),
-
+            } => {
+                self.data.update_indices(cluster_name, response);
+                None
+            }
+            ElasticsearchResponseEvent::AllClusterHealth { healths, outcome } => {
+                let selected = self.selected_cluster_name().map(str::to_owned);
+                for (cluster_name, health) in healths {
+                    self.update_cluster_health(cluster_name, health);
+                }
+                for (cluster_name, reason) in outcome.failed.iter().cloned() {
+                    self.data
+                        .update_fetch_status(cluster_name, Cluster, FetchStatus::Err(reason));
+                }
+                self.data.update_fan_out("cluster health", outcome);
+                self.select_cluster(selected.as_deref());
+                None
+            }
+            ElasticsearchResponseEvent::Nodes {
+                cluster_name,
+                response,
+            } => {
+                self.data.update_nodes(cluster_name, response);
+                None
+            }
             ElasticsearchResponseEvent::Aliases {
                 cluster_name,
                 response,
-            } => self.data.update_aliases(cluster_name, response),
+            } => {
+                self.data.update_aliases(cluster_name, response);
+                None
+            }
+            ElasticsearchResponseEvent::Templates {
+                cluster_name,
+                response,
+            } => {
+                self.data.update_templates(cluster_name, response);
+                None
+            }
+            ElasticsearchResponseEvent::IlmPolicies {
+                cluster_name,
+                response,
+            } => {
+                self.data.update_ilm_policies(cluster_name, response);
+                None
+            }
+            ElasticsearchResponseEvent::Repositories {
+                cluster_name,
+                response,
+            } => {
+                self.data.update_repositories(cluster_name, response);
+                // snapshots of the selected repository are shown along with repositories.
+                self.fetch_snapshots()
+            }
+            ElasticsearchResponseEvent::Snapshots {
+                cluster_name,
+                repository,
+                response,
+            } => {
+                self.data
+                    .update_snapshots(cluster_name, repository, response);
+                None
+            }
+            ElasticsearchResponseEvent::SampleDocs {
+                cluster_name,
+                index,
+                from,
+                response,
+            } => {
+                self.data
+                    .update_sample_docs(cluster_name, index, from, response);
+                None
+            }
+            ElasticsearchResponseEvent::IndexSettings {
+                cluster_name,
+                index,
+                response,
+            } => {
+                self.data
+                    .update_index_settings(cluster_name, index, response);
+                None
+            }
+            ElasticsearchResponseEvent::IndexMappings {
+                cluster_name,
+                index,
+                response,
+            } => {
+                self.data
+                    .update_index_mappings(cluster_name, index, response);
+                None
+            }
+            ElasticsearchResponseEvent::ClusterSettings {
+                cluster_name,
+                response,
+            } => {
+                self.data.update_cluster_settings(cluster_name, response);
+                None
+            }
+            ElasticsearchResponseEvent::AllocationExplain {
+                cluster_name,
+                response,
+            } => {
+                self.data.update_allocation_explain(cluster_name, response);
+                None
+            }
+            ElasticsearchResponseEvent::Tasks {
+                cluster_name,
+                response,
+            } => {
+                self.data.update_tasks(cluster_name, response);
+                None
+            }
+            ElasticsearchResponseEvent::SearchAllClusters {
+                pattern,
+                indices,
+                outcome,
+            } => {
+                self.data.update_global_search(GlobalSearchResult::new(
+                    pattern,
+                    indices,
+                    outcome.failed.clone(),
+                ));
+                self.data.update_fan_out("search", outcome);
+                None
+            }
+            ElasticsearchResponseEvent::RawResponse {
+                cluster_name,
+                path,
+                response,
+            } => {
+                self.data
+                    .update_raw_response(RawResult::new(cluster_name, path, response));
+                None
+            }
         };
+        self.send(fetch)
     }
 
-    pub(crate) fn focus(&mut self, component: ElasticsearchComponentKind) {
+    pub(crate) fn focus(
+        &mut self,
+        component: ElasticsearchComponentKind,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        if component.is_left_panel() {
+            self.state.left_panel_collapsed = false;
+        }
+        if component == IndexPalette {
+            self.state.index_palette_query.clear();
+            self.state.index_palette_list_state.select(Some(0));
+        }
         self.state.focused = Some(component);
+
+        // the left panel only selects what to show.
+        let fetch = if self.refresh_on_focus && !component.is_left_panel() {
+            self.fetch_data()
+        } else {
+            None
+        };
+        self.send(fetch)
     }
 
-    pub(crate) fn unfocus(&mut self) {
-        self.state.focused = None;
+    pub(crate) fn focused(&self) -> Option<ElasticsearchComponentKind> {
+        self.state.focused
     }
 
-    pub(crate) fn navigate(
+    /// Switch to the next filter preset, after the last one all rows are shown.
+    pub(crate) fn cycle_filter_preset(
         &mut self,
         component: ElasticsearchComponentKind,
-        navigate: Navigate,
     ) -> Option<impl Iterator<Item = RequestEvent>> {
         let fetch = match component {
-            ClusterList => {
-                self.state
-                    .cluster_list_state
-                    .apply(navigate, self.cluster_names().count());
-                true
-            }
-            ResourceList => {
-                self.state
-                    .resource_list_state
-                    .apply(navigate, self.resources.len());
-                true
+            IndexTable if !self.index_filter_presets.is_empty() => {
+                self.state.index_filter_preset = match self.state.index_filter_preset {
+                    None => Some(0),
+                    Some(i) if i + 1 < self.index_filter_presets.len() => Some(i + 1),
+                    Some(_) => None,
+                };
+                self.state.index_table_state.select(Some(0));
+                // the preset pattern is pushed down to the api.
+                self.fetch_data()
             }
+            _ => None,
+        };
+        self.send(fetch)
+    }
+
+    /// Expand the selected row in place, or collapse it if it is already expanded.
+    pub(crate) fn toggle_expand(
+        &mut self,
+        component: ElasticsearchComponentKind,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        let fetch = match component {
             IndexTable => {
-                self.state.index_table_state.apply(
-                    navigate,
-                    self.selected_cluster_name()
-                        .and_then(|c| self.data.get_visible_indices(c))
-                        .map(|iter| iter.count())
-                        .unwrap_or(0),
-                );
-                false
+                let selected = self.state.index_table_state.selected();
+                let group = match selected.and_then(|i| self.index_rows().into_iter().nth(i)) {
+                    Some(IndexRow::Group { prefix, .. }) => Some(prefix.to_owned()),
+                    _ => None,
+                };
+                if let Some(prefix) = group {
+                    if self.state.expanded_index_group.as_deref() == Some(prefix.as_str()) {
+                        self.state.expanded_index_group = None;
+                    } else {
+                        self.state.expanded_index_group = Some(prefix);
+                    }
+                    return None;
+                }
+
+                let (cluster, index) = self.selected_cluster_name().zip(self.selected_index())?;
+                if self.state.expanded_index.as_deref() == Some(index.index.as_str()) {
+                    self.state.expanded_index = None;
+                    None
+                } else {
+                    let fetch = vec![ElasticsearchRequestEvent::FetchIndexMappings {
+                        cluster_name: cluster.to_owned(),
+                        index: index.index.clone(),
+                    }];
+                    self.state.expanded_index = Some(index.index.clone());
+                    Some(fetch)
+                }
             }
             AliasTable => {
-                self.state.alias_table_state.apply(
-                    navigate,
-                    self.selected_cluster_name()
-                        .and_then(|c| self.data.get_visible_aliases(c).map(|iter| iter.count()))
-                        .unwrap_or(0),
-                );
-                false
+                let alias = self
+                    .selected_cluster_name()
+                    .and_then(|cluster| self.alias_rows(cluster))
+                    .and_then(|rows| {
+                        let selected = self.state.alias_table_state.selected()?;
+                        match rows.get(selected)? {
+                            AliasRow::Alias(_) => None,
+                            row => Some(row.alias().to_owned()),
+                        }
+                    })?;
+                if self.state.expanded_alias.as_deref() == Some(alias.as_str()) {
+                    self.state.expanded_alias = None;
+                } else {
+                    self.state.expanded_alias = Some(alias);
+                }
+                None
             }
+            _ => None,
         };
-        if fetch {
-            self.fetch_data()
-                .map(|events| events.into_iter().map(RequestEvent::Elasticsearch))
-        } else {
-            None
+        self.send(fetch)
+    }
+
+    pub(crate) fn cycle_sort(&mut self, component: ElasticsearchComponentKind) {
+        match component {
+            ClusterList => {
+                let selected = self.selected_cluster_name().map(str::to_owned);
+                self.state.cluster_sort = self.state.cluster_sort.next();
+                self.select_cluster(selected.as_deref());
+            }
+            IndexTable => {
+                self.state.index_sort = match self.state.index_sort {
+                    IndexSort::Name => IndexSort::Health,
+                    IndexSort::Health => IndexSort::StoreSize,
+                    IndexSort::StoreSize => IndexSort::Name,
+                };
+                self.state.index_table_state.select(Some(0));
+            }
+            AliasTable => {
+                self.state.alias_sort = self.state.alias_sort.next();
+                self.state.alias_table_state.select(Some(0));
+            }
+            _ => (),
         }
     }
 
-    fn cluster_names(&self) -> impl Iterator<Item = &str> {
-        self.configs.iter().map(|c| c.name.as_str())
+    pub(crate) fn adjust_column(
+        &mut self,
+        component: ElasticsearchComponentKind,
+        adjust: ColumnAdjust,
+    ) {
+        /// Characters changed per key press.
+        const STEP: i16 = 2;
+
+        if component != IndexTable {
+            return;
+        }
+        let columns = self.visible_index_columns();
+        let active = &mut self.state.index_active_column;
+        match adjust {
+            ColumnAdjust::Prev => *active = active.saturating_sub(1),
+            ColumnAdjust::Next => *active = cmp::min(*active + 1, columns.len().saturating_sub(1)),
+            ColumnAdjust::Widen | ColumnAdjust::Narrow => {
+                if let Some(column) = columns.get(*active) {
+                    let step = if adjust == ColumnAdjust::Widen {
+                        STEP
+                    } else {
+                        -STEP
+                    };
+                    let delta = self.state.index_column_widths.entry(*column).or_default();
+                    *delta += step;
+                    // bound the delta as render does, so widening always shows.
+                    if let Some(&default) = self.state.index_column_default_widths.get(column) {
+                        let default = default as i16;
+                        *delta =
+                            (*delta).clamp(MIN_COLUMN_WIDTH - default, MAX_COLUMN_WIDTH - default);
+                    }
+                }
+            }
+            ColumnAdjust::Reset => self.state.index_column_widths.clear(),
+        }
+    }
+
+    /// Columns of the index table in order, as configured and changed by the display modes.
+    fn visible_index_columns(&self) -> Vec<IndexColumn> {
+        self.index_columns
+            .iter()
+            .map(|column| match column {
+                // primary and replica columns are replaced with the "pri/rep" column.
+                IndexColumn::Primary | IndexColumn::Replica if self.merge_shard_columns => {
+                    IndexColumn::Shards
+                }
+                column => *column,
+            })
+            // uuid and primary store size move to the second line of the row in wide mode.
+            .filter(|column| {
+                !(self.state.wide_rows
+                    && matches!(column, IndexColumn::PrimaryStoreSize | IndexColumn::Uuid))
+            })
+            .filter(|column| {
+                self.state.index_table_width.unwrap_or(u16::MAX) >= index_column_min_width(*column)
+            })
+            .unique()
+            .collect()
+    }
+
+    /// Select the next cluster whose name starts with the character, cycling on repeated presses.
+    pub(crate) fn jump_to_initial(
+        &mut self,
+        component: ElasticsearchComponentKind,
+        initial: char,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        let fetch = match component {
+            ClusterList => {
+                let next = {
+                    let names: Vec<&str> = self.cluster_names().collect();
+                    let current = self.state.cluster_list_state.selected().unwrap_or(0);
+                    let initial = initial.to_lowercase().to_string();
+                    (1..=names.len())
+                        .map(|offset| (current + offset) % names.len())
+                        .find(|&i| names[i].to_lowercase().starts_with(initial.as_str()))
+                };
+                match next {
+                    Some(i) => {
+                        self.state.cluster_list_state.select(Some(i));
+                        self.fetch_data()
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+        self.send(fetch)
+    }
+
+    /// Select the index picked in the palette in the index table and focus it.
+    fn open_palette_index(&mut self) -> Option<Vec<ElasticsearchRequestEvent>> {
+        let picked = self
+            .state
+            .index_palette_list_state
+            .selected()
+            .and_then(|i| {
+                self.index_palette_matches()
+                    .get(i)
+                    .map(|index| index.index.clone())
+            })?;
+
+        // list the indices of the collapsed group to select the picked one.
+        if self.state.group_indices {
+            self.state.expanded_index_group =
+                index_prefix(&picked, self.index_group_pattern.as_ref()).map(str::to_owned);
+        }
+        let position = self
+            .index_rows()
+            .iter()
+            .position(|row| matches!(row.index(), Some(index) if index.index == picked));
+        self.state.index_table_state.select(position);
+        self.state.focused = Some(IndexTable);
+
+        if self.selected_resource() == Some(Index) {
+            return None;
+        }
+        let resource = self.resources.iter().position(|r| *r == Index);
+        self.state.resource_list_state.select(resource);
+        self.fetch_data()
+    }
+
+    /// Keep the selection on the same cluster after the list is reordered.
+    fn select_cluster(&mut self, cluster_name: Option<&str>) {
+        if let Some(position) =
+            cluster_name.and_then(|selected| self.cluster_names().position(|name| name == selected))
+        {
+            self.state.cluster_list_state.select(Some(position));
+        }
+    }
+
+    pub(crate) fn toggle_density(&mut self) {
+        self.state.wide_rows = !self.state.wide_rows;
+    }
+
+    pub(crate) fn toggle_follow(&mut self) {
+        self.state.follow = !self.state.follow;
+    }
+
+    pub(crate) fn is_following(&self) -> bool {
+        self.state.follow
+    }
+
+    /// Time left until the changes of the selected cluster's indices stop being highlighted.
+    pub(crate) fn highlight_remaining(&self) -> Option<Duration> {
+        self.selected_cluster_name()
+            .and_then(|name| self.data.get_index_diff(name))
+            .and_then(|diff| diff.remaining())
+    }
+
+    /// Poll the health of the selected cluster unless the previous poll is still in flight.
+    pub(crate) fn follow_tick(&mut self) -> Option<impl Iterator<Item = RequestEvent>> {
+        let fetch = self
+            .selected_cluster_name()
+            .filter(|_| self.state.follow)
+            .filter(|name| self.data.get_fetch_status(name, Cluster) != FetchStatus::InFlight)
+            .map(|name| {
+                vec![ElasticsearchRequestEvent::FetchCluster {
+                    cluster_name: name.to_owned(),
+                }]
+            });
+        self.send(fetch)
+    }
+
+    /// Selections to restore on the next launch.
+    pub(crate) fn session_state(&self) -> SessionState {
+        SessionState {
+            cluster: self.selected_cluster_name().map(str::to_owned),
+            resource: self
+                .selected_resource()
+                .map(|resource| resource.to_string()),
+            index_filter: self
+                .state
+                .index_filter_preset
+                .and_then(|position| self.index_filter_presets.get(position))
+                .map(|(name, _)| name.clone()),
+            index_sort: Some(self.state.index_sort),
+            // the view fills its own state.
+            ..Default::default()
+        }
+    }
+
+    /// Visible components of the selected resource in the order the focus cycles through them.
+    pub(crate) fn focus_cycle(&self) -> Vec<ElasticsearchComponentKind> {
+        let mut components = Vec::new();
+        if !self.state.left_panel_collapsed {
+            components.extend([ClusterList, ResourceList]);
+        }
+        match self.selected_resource() {
+            Some(Dashboard) => components.push(DashboardTable),
+            Some(Node) => components.push(NodeTable),
+            Some(Index) => components.push(IndexTable),
+            Some(Alias) => components.push(AliasTable),
+            Some(Template) => components.push(TemplateTable),
+            Some(Ilm) => components.push(IlmPolicyList),
+            Some(Snapshot) => components.extend([RepositoryList, SnapshotTable]),
+            Some(Settings) => components.push(SettingTable),
+            Some(Task) => components.push(TaskTable),
+            // the allocation is entered from the cluster list.
+            Some(Cluster) | None => (),
+        }
+        components
+    }
+
+    /// Clear the filter and the sort of the selected resource and select the first row again.
+    pub(crate) fn reset(&mut self) -> Option<impl Iterator<Item = RequestEvent>> {
+        let mut fetch = None;
+        match self.selected_resource() {
+            Some(Cluster) => self.state.allocation_detail_scroll = 0,
+            Some(Dashboard) => self.state.dashboard_table_state.select(Some(0)),
+            Some(Node) => self.state.node_table_state.select(Some(0)),
+            Some(Index) => {
+                // indices were fetched with the preset pattern.
+                if self.state.index_filter_preset.take().is_some() {
+                    fetch = self.fetch_data();
+                }
+                self.state.index_sort = self.default_index_sort;
+                self.state.expanded_index_group = None;
+                self.state.index_table_state.select(Some(0));
+                self.state.index_detail_scroll = 0;
+            }
+            Some(Alias) => {
+                self.state.alias_sort = AliasSort::default();
+                self.state.alias_table_state.select(Some(0));
+            }
+            Some(Template) => self.state.template_table_state.select(Some(0)),
+            Some(Ilm) => self.state.ilm_policy_list_state.select(Some(0)),
+            Some(Snapshot) => {
+                self.state.repository_list_state.select(Some(0));
+                self.state.snapshot_table_state.select(Some(0));
+            }
+            Some(Settings) => {
+                self.state.setting_filter = TableFilter::default();
+                self.state.setting_table_state.select(Some(0));
+            }
+            Some(Task) => self.state.task_table_state.select(Some(0)),
+            None => (),
+        }
+        self.send(fetch)
+    }
+
+    pub(crate) fn toggle_redact(&mut self) {
+        self.state.redactor.toggle();
+    }
+
+    pub(crate) fn toggle_fan_out(&mut self) {
+        self.state.show_fan_out = !self.state.show_fan_out;
+    }
+
+    pub(crate) fn toggle_health_log(&mut self) {
+        self.state.show_health_log = !self.state.show_health_log;
+    }
+
+    pub(crate) fn toggle_size_scope(&mut self) {
+        self.state.size_scope = self.state.size_scope.toggle();
+    }
+
+    pub(crate) fn cycle_byte_display(&mut self) {
+        self.state.byte_display = self.state.byte_display.next();
+    }
+
+    pub(crate) fn toggle_compact_json(&mut self) {
+        self.state.compact_json = !self.state.compact_json;
+    }
+
+    pub(crate) fn toggle_relative_timestamps(&mut self) {
+        self.state.relative_timestamps = !self.state.relative_timestamps;
+    }
+
+    pub(crate) fn toggle_index_groups(&mut self) {
+        self.state.group_indices = !self.state.group_indices;
+        self.state.expanded_index_group = None;
+        self.state.index_table_state.select(Some(0));
+    }
+
+    pub(crate) fn toggle_hidden_indices(&mut self) {
+        self.state.show_hidden_indices = !self.state.show_hidden_indices;
+        self.state.index_table_state.select(Some(0));
+    }
+
+    /// Toggle the left panel visibility, return whether the panel is collapsed.
+    pub(crate) fn toggle_left_panel(&mut self) -> bool {
+        self.state.left_panel_collapsed = !self.state.left_panel_collapsed;
+        self.state.left_panel_collapsed
+    }
+
+    pub(crate) fn unfocus(&mut self) {
+        self.state.focused = None;
+    }
+
+    /// Enter the component, return the entered component if the component can be entered.
+    pub(crate) fn enter(
+        &mut self,
+        component: ElasticsearchComponentKind,
+    ) -> Option<ElasticsearchComponentKind> {
+        let entered = match component {
+            IndexTable if self.selected_index().is_some() => {
+                self.state.index_detail_scroll = 0;
+                IndexDetail
+            }
+            // why shards of the cluster are unassigned.
+            ClusterList if self.selected_resource() == Some(Cluster) => {
+                self.state.allocation_detail_scroll = 0;
+                AllocationDetail
+            }
+            _ => return None,
+        };
+        self.state.entered = Some(entered);
+        Some(entered)
+    }
+
+    /// Fetch the next page of the sample documents of the entered index.
+    /// Err with the reason if there is no page to fetch.
+    pub(crate) fn load_more(
+        &mut self,
+    ) -> Result<Option<impl Iterator<Item = RequestEvent>>, String> {
+        if self.state.entered != Some(IndexDetail) {
+            return Ok(None);
+        }
+        let (cluster, index) = match self.selected_cluster_name().zip(self.selected_index()) {
+            Some(selected) => selected,
+            None => return Ok(None),
+        };
+        let hits = match self.data.get_sample_docs(cluster, &index.index) {
+            Some(docs) => &docs.hits,
+            None => return Ok(None),
+        };
+
+        let from = hits.hits.len() as i64;
+        if matches!(&hits.total, Some(total) if from as u64 >= total.value) {
+            return Err("no more documents".to_owned());
+        }
+        let size = self
+            .configs
+            .iter()
+            .find(|c| c.name == cluster)
+            .and_then(|c| c.sample_docs_size)
+            .unwrap_or(5);
+        if from + i64::from(size) > MAX_RESULT_WINDOW {
+            return Err(format!(
+                "can not page beyond {} documents",
+                format_count(MAX_RESULT_WINDOW, &self.thousands_separator)
+            ));
+        }
+
+        let fetch = vec![ElasticsearchRequestEvent::FetchSampleDocs {
+            cluster_name: cluster.to_owned(),
+            index: index.index.clone(),
+            from,
+        }];
+        Ok(self.send(Some(fetch)))
+    }
+
+    /// `GET` the typed path from the selected cluster.
+    fn raw_get(&mut self) -> Option<Vec<ElasticsearchRequestEvent>> {
+        let path = self.state.raw_get_path.trim().to_owned();
+        if path.is_empty() {
+            return None;
+        }
+        let cluster_name = self.selected_cluster_name()?.to_owned();
+        self.state.raw_response_scroll = 0;
+
+        Some(vec![ElasticsearchRequestEvent::RawRequest {
+            cluster_name,
+            path,
+        }])
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.state.entered = None;
+    }
+
+    pub(crate) fn edit(
+        &mut self,
+        component: ElasticsearchComponentKind,
+        edit: TextEdit,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        let fetch = match (component, edit) {
+            (GlobalSearch, TextEdit::Push(c)) => {
+                self.state.global_search_query.push(c);
+                None
+            }
+            (GlobalSearch, TextEdit::Pop) => {
+                self.state.global_search_query.pop();
+                None
+            }
+            (GlobalSearch, TextEdit::Submit) => {
+                self.state.global_search_table_state.select(Some(0));
+                Some(vec![ElasticsearchRequestEvent::SearchAllClusters {
+                    pattern: self.state.global_search_query.clone(),
+                }])
+            }
+            (IndexPalette, TextEdit::Push(c)) => {
+                self.state.index_palette_query.push(c);
+                self.state.index_palette_list_state.select(Some(0));
+                None
+            }
+            (IndexPalette, TextEdit::Pop) => {
+                self.state.index_palette_query.pop();
+                self.state.index_palette_list_state.select(Some(0));
+                None
+            }
+            (IndexPalette, TextEdit::Submit) => self.open_palette_index(),
+            (RawGet, TextEdit::Push(c)) => {
+                self.state.raw_get_path.push(c);
+                None
+            }
+            (RawGet, TextEdit::Pop) => {
+                self.state.raw_get_path.pop();
+                None
+            }
+            (RawGet, TextEdit::Submit) => self.raw_get(),
+            (SettingTable, TextEdit::Push(c)) => {
+                self.state.setting_filter.push(c);
+                self.state.setting_table_state.select(Some(0));
+                None
+            }
+            (SettingTable, TextEdit::Pop) => {
+                self.state.setting_filter.pop();
+                self.state.setting_table_state.select(Some(0));
+                None
+            }
+            (SettingTable, TextEdit::CycleMode) => {
+                self.state.setting_filter.cycle_mode();
+                self.state.setting_table_state.select(Some(0));
+                None
+            }
+            _ => None,
+        };
+        self.send(fetch)
+    }
+
+    pub(crate) fn navigate(
+        &mut self,
+        component: ElasticsearchComponentKind,
+        navigate: Navigate,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        let fetch = match component {
+            ClusterList => {
+                self.state
+                    .cluster_list_state
+                    .apply(navigate, self.cluster_names().count());
+                self.fetch_data()
+            }
+            ResourceList => {
+                self.state
+                    .resource_list_state
+                    .apply(navigate, self.resources.len());
+                self.fetch_data()
+            }
+            IndexTable => {
+                let rows = self.index_rows().len();
+                self.state.index_table_state.apply(navigate, rows);
+                None
+            }
+            DashboardTable => {
+                self.state
+                    .dashboard_table_state
+                    .apply(navigate, self.cluster_names().count());
+                None
+            }
+            NodeTable => {
+                self.state.node_table_state.apply(
+                    navigate,
+                    self.selected_cluster_name()
+                        .and_then(|c| self.data.get_nodes(c))
+                        .map(|nodes| nodes.len())
+                        .unwrap_or(0),
+                );
+                None
+            }
+            AliasTable => {
+                self.state.alias_table_state.apply(
+                    navigate,
+                    self.selected_cluster_name()
+                        .and_then(|c| self.alias_rows(c).map(|rows| rows.len()))
+                        .unwrap_or(0),
+                );
+                None
+            }
+            TemplateTable => {
+                self.state.template_table_state.apply(
+                    navigate,
+                    self.selected_cluster_name()
+                        .and_then(|c| self.data.get_visible_templates(c).map(|iter| iter.count()))
+                        .unwrap_or(0),
+                );
+                None
+            }
+            IlmPolicyList => {
+                self.state.ilm_policy_list_state.apply(
+                    navigate,
+                    self.selected_cluster_name()
+                        .and_then(|c| self.data.get_visible_ilm_policies(c))
+                        .flatten()
+                        .map(|iter| iter.count())
+                        .unwrap_or(0),
+                );
+                None
+            }
+            RepositoryList => {
+                self.state.repository_list_state.apply(
+                    navigate,
+                    self.selected_cluster_name()
+                        .and_then(|c| self.data.get_repositories(c).map(|iter| iter.count()))
+                        .unwrap_or(0),
+                );
+                self.state.snapshot_table_state.select(Some(0));
+                self.fetch_snapshots()
+            }
+            IndexDetail => {
+                self.state.index_detail_scroll = match navigate {
+                    Navigate::Up => self.state.index_detail_scroll.saturating_sub(1),
+                    Navigate::Down => self.state.index_detail_scroll.saturating_add(1),
+                    _ => self.state.index_detail_scroll,
+                };
+                None
+            }
+            RawGet => {
+                self.state.raw_response_scroll = match navigate {
+                    Navigate::Up => self.state.raw_response_scroll.saturating_sub(1),
+                    Navigate::Down => self.state.raw_response_scroll.saturating_add(1),
+                    _ => self.state.raw_response_scroll,
+                };
+                None
+            }
+            AllocationDetail => {
+                self.state.allocation_detail_scroll = match navigate {
+                    Navigate::Up => self.state.allocation_detail_scroll.saturating_sub(1),
+                    Navigate::Down => self.state.allocation_detail_scroll.saturating_add(1),
+                    _ => self.state.allocation_detail_scroll,
+                };
+                None
+            }
+            GlobalSearch => {
+                self.state.global_search_table_state.apply(
+                    navigate,
+                    self.data
+                        .get_global_search()
+                        .map(|result| result.indices.len())
+                        .unwrap_or(0),
+                );
+                None
+            }
+            SettingTable => {
+                let len = self.visible_settings().len();
+                self.state.setting_table_state.apply(navigate, len);
+                None
+            }
+            IndexPalette => {
+                let len = self.index_palette_matches().len();
+                self.state.index_palette_list_state.apply(navigate, len);
+                None
+            }
+            TaskTable => {
+                self.state.task_table_state.apply(
+                    navigate,
+                    self.selected_cluster_name()
+                        .and_then(|c| self.data.get_tasks(c))
+                        .map(|tasks| tasks.len())
+                        .unwrap_or(0),
+                );
+                None
+            }
+            SnapshotTable => {
+                self.state.snapshot_table_state.apply(
+                    navigate,
+                    self.selected_cluster_name()
+                        .zip(self.selected_repository_name())
+                        .and_then(|(c, r)| self.data.get_snapshots(c, r).map(|iter| iter.count()))
+                        .unwrap_or(0),
+                );
+                None
+            }
+        };
+        self.send(fetch)
+    }
+
+    /// Cluster names in the order of the cluster list.
+    fn cluster_names(&self) -> impl Iterator<Item = &str> {
+        let mut names: Vec<&str> = self.configs.iter().map(|c| c.name.as_str()).collect();
+        match self.state.cluster_sort {
+            ClusterSort::Config => (),
+            ClusterSort::Name => names.sort_unstable(),
+            ClusterSort::Health => names.sort_by_key(|name| {
+                let health = self
+                    .data
+                    .get_cluster_health(name)
+                    .map(|health| health.status.as_str());
+                (health_order(health), *name)
+            }),
+        }
+        names.into_iter()
     }
 
     fn selected_cluster_name(&self) -> Option<&str> {
@@ -211,6 +1646,160 @@ impl ElasticsearchComponent {
             .and_then(|i| self.cluster_names().nth(i))
     }
 
+    fn selected_repository_name(&self) -> Option<&str> {
+        self.selected_cluster_name()
+            .and_then(|c| self.data.get_repositories(c))
+            .zip(self.state.repository_list_state.selected())
+            .and_then(|(mut repositories, i)| repositories.nth(i))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Selected index in the index table.
+    fn selected_index(&self) -> Option<&CatIndex> {
+        let rows = self.index_rows();
+
+        self.state
+            .index_table_state
+            .selected()
+            .and_then(|i| rows.get(i))
+            .and_then(IndexRow::index)
+    }
+
+    /// Format of the timestamps shown, relative or in the configured timezone.
+    fn timestamp_format(&self) -> TimestampFormat {
+        TimestampFormat {
+            tz: self.timezone,
+            relative: self.state.relative_timestamps,
+        }
+    }
+
+    /// Rows of the index table, grouped by the prefix if enabled.
+    fn index_rows(&self) -> Vec<IndexRow<'_>> {
+        index_rows(
+            &self.sorted_visible_indices(),
+            self.state.group_indices,
+            self.index_group_pattern.as_ref(),
+            self.state.expanded_index_group.as_deref(),
+        )
+    }
+
+    /// Rows of the index table in order.
+    fn sorted_visible_indices(&self) -> Vec<&CatIndex> {
+        let mut indices: Vec<&CatIndex> = self
+            .selected_cluster_name()
+            .and_then(|name| {
+                self.data.get_visible_indices(
+                    name,
+                    self.index_filter(),
+                    self.state.show_hidden_indices,
+                )
+            })
+            .map(Iterator::collect)
+            .unwrap_or_default();
+        sort_indices(&mut indices, self.state.index_sort);
+        indices
+    }
+
+    /// Rows of the index table matching the palette query, the best match first.
+    fn index_palette_matches(&self) -> Vec<&CatIndex> {
+        let mut matches: Vec<(u32, &CatIndex)> = self
+            .sorted_visible_indices()
+            .into_iter()
+            .filter_map(|index| {
+                fuzzy_score(&self.state.index_palette_query, &index.index)
+                    .map(|score| (score, index))
+            })
+            .collect();
+        // shorter names are closer to the query among the same score.
+        matches
+            .sort_by_key(|&(score, index)| (cmp::Reverse(score), index.index.len(), &index.index));
+        matches.into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// Cluster settings whose key matches the filter.
+    fn visible_settings(&self) -> Vec<(&'static str, &String, &serde_json::Value)> {
+        self.selected_cluster_name()
+            .and_then(|name| self.data.get_cluster_settings(name))
+            .map(|settings| {
+                settings
+                    .filter(|(_, key, _)| self.state.setting_filter.matches(key))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn index_filter(&self) -> Option<&TableFilter> {
+        self.state
+            .index_filter_preset
+            .and_then(|i| self.index_filter_presets.get(i))
+            .map(|(_, filter)| filter)
+    }
+
+    /// Curl command fetching the selected resource of the selected cluster.
+    pub(crate) fn selected_curl(&self) -> Option<String> {
+        let cluster_name = self.selected_cluster_name()?;
+        let config = self.configs.iter().find(|c| c.name == cluster_name)?;
+        self.fetch_data()?
+            .first()
+            .and_then(|req| req.to_curl(config))
+    }
+
+    pub(crate) fn selected_path(&self) -> Option<String> {
+        let cluster_name = self.selected_cluster_name()?;
+        let config = self.configs.iter().find(|c| c.name == cluster_name)?;
+        self.fetch_data()?
+            .first()
+            .and_then(|req| req.to_path(config))
+    }
+
+    /// Kibana index management of the selected index, or the index endpoint if Kibana is not configured.
+    pub(crate) fn selected_index_url(&self) -> Option<Url> {
+        if self.selected_resource() != Some(Index) {
+            return None;
+        }
+        let cluster_name = self.selected_cluster_name()?;
+        let config = self.configs.iter().find(|c| c.name == cluster_name)?;
+        let index = self.selected_index()?;
+
+        let (mut url, segments) = match config.kibana_url.as_ref() {
+            Some(kibana) => (
+                kibana.clone(),
+                vec![
+                    "app",
+                    "management",
+                    "data",
+                    "index_management",
+                    "indices",
+                    "filter",
+                    index.index.as_str(),
+                ],
+            ),
+            None => (config.endpoint.clone(), vec![index.index.as_str()]),
+        };
+        url.path_segments_mut()
+            .ok()?
+            .pop_if_empty()
+            .extend(segments);
+        Some(url)
+    }
+
+    pub(crate) fn redactor(&self) -> &Redactor {
+        &self.state.redactor
+    }
+
+    /// Selected cluster name in its label color for the status bar.
+    pub(crate) fn selected_cluster_label(&self) -> Option<Span<'static>> {
+        let name = self.selected_cluster_name()?;
+        let style = match self.label_colors.get(name) {
+            Some(color) => Style::default().fg(*color).add_modifier(Modifier::BOLD),
+            None => Style::default().add_modifier(Modifier::BOLD),
+        };
+        Some(Span::styled(
+            self.state.redactor.cluster(name).into_owned(),
+            style,
+        ))
+    }
+
     fn selected_resource(&self) -> Option<ElasticsearchResourceKind> {
         self.state
             .resource_list_state
@@ -222,30 +1811,245 @@ impl ElasticsearchComponent {
     where
         B: tui::backend::Backend,
     {
-        let (left_area, resource_area) = {
+        let resource_area = if self.state.left_panel_collapsed {
+            ctx.rect
+        } else {
             let chunks = Layout::default()
                 .direction(Horizontal)
                 .margin(0)
                 .constraints([Constraint::Length(20), Constraint::Percentage(100)].as_ref())
                 .split(ctx.rect);
-            (chunks[0], chunks[1])
+
+            self.render_left(ctx.with(chunks[0]));
+            chunks[1]
         };
 
-        self.render_left(ctx.with(left_area));
+        if self.state.focused == Some(GlobalSearch) {
+            self.render_global_search(ctx.with(resource_area));
+            return;
+        }
+        if self.state.focused == Some(RawGet) {
+            self.render_raw_get(ctx.with(resource_area));
+            return;
+        }
 
         match self.selected_resource() {
             Some(Cluster) => self.render_cluster(ctx.with(resource_area)),
+            Some(Index) if self.state.entered == Some(IndexDetail) => {
+                self.render_index_detail(ctx.with(resource_area))
+            }
             Some(Index) => self.render_index(ctx.with(resource_area)),
+            Some(Dashboard) => self.render_dashboard(ctx.with(resource_area)),
+            Some(Node) => self.render_nodes(ctx.with(resource_area)),
             Some(Alias) => self.render_aliases(ctx.with(resource_area)),
+            Some(Template) => self.render_templates(ctx.with(resource_area)),
+            Some(Ilm) => self.render_ilm_policies(ctx.with(resource_area)),
+            Some(Snapshot) => self.render_snapshots(ctx.with(resource_area)),
+            Some(Settings) => self.render_settings(ctx.with(resource_area)),
+            Some(Task) => self.render_tasks(ctx.with(resource_area)),
             None => (),
         }
+
+        // keep showing the last data but make it clear it is outdated.
+        if self
+            .selected_resource()
+            .and_then(|resource| self.stale_since(resource))
+            .is_some()
+        {
+            ctx.frame.render_widget(Dimmed, resource_area);
+        }
+
+        if self.state.focused == Some(IndexPalette) {
+            self.render_index_palette(ctx.with(resource_area));
+        }
+        if self.state.show_fan_out {
+            self.render_fan_out(ctx.with(resource_area));
+        }
+        if self.state.show_health_log {
+            self.render_health_log(ctx.with(resource_area));
+        }
     }
 
-    fn render_left<B>(&mut self, ctx: &mut ViewContext<B>)
+    /// Render the breakdown of the summarized outcome over the resource.
+    fn render_fan_out<B>(&mut self, ctx: &mut ViewContext<B>)
     where
         B: tui::backend::Backend,
     {
-        let (cluster_list_area, resource_list_area) = {
+        let (title, lines) = match self.data.get_fan_out() {
+            Some((request, outcome)) => {
+                let ok = outcome.ok.iter().map(|cluster| {
+                    Spans::from(vec![
+                        Span::styled("  ok      ", Style::default().fg(Color::Green)),
+                        Span::raw(self.state.redactor.cluster(cluster).into_owned()),
+                    ])
+                });
+                let failed = outcome.failed.iter().map(|(cluster, reason)| {
+                    Spans::from(vec![
+                        Span::styled("  failed  ", Style::default().fg(Color::Red)),
+                        Span::raw(self.state.redactor.cluster(cluster).into_owned()),
+                        Span::styled(
+                            format!("  {reason}"),
+                            Style::default().add_modifier(Modifier::DIM),
+                        ),
+                    ])
+                });
+                (
+                    format!("{request}: {}", outcome.summary()),
+                    failed.chain(ok).collect::<Vec<_>>(),
+                )
+            }
+            None => (
+                "All Clusters".to_owned(),
+                vec![Spans::from("  no request to all clusters yet")],
+            ),
+        };
+
+        let area = popup_area(ctx, lines.len());
+        ctx.frame.render_widget(Clear, area);
+        ctx.frame.render_widget(
+            Paragraph::new(lines).block(ctx.style.block(false).title(title)),
+            area,
+        );
+    }
+
+    /// Render the health transitions over the resource, the latest first.
+    fn render_health_log<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let log = self.data.get_health_log();
+        let lines: Vec<Spans> = if log.is_empty() {
+            vec![Spans::from("  no health change yet")]
+        } else {
+            log.iter()
+                .rev()
+                .map(|change| {
+                    Spans::from(vec![
+                        Span::styled(
+                            format!("  {}  ", self.timestamp_format().system_time(change.at)),
+                            Style::default().add_modifier(Modifier::DIM),
+                        ),
+                        Span::raw(
+                            self.state
+                                .redactor
+                                .cluster(&change.cluster_name)
+                                .into_owned(),
+                        ),
+                        Span::raw("  "),
+                        Span::styled(
+                            change.from.clone(),
+                            Style::default().fg(health_color(&change.from)),
+                        ),
+                        Span::raw(" → "),
+                        Span::styled(
+                            change.to.clone(),
+                            Style::default().fg(health_color(&change.to)),
+                        ),
+                    ])
+                })
+                .collect()
+        };
+
+        let area = popup_area(ctx, lines.len());
+        ctx.frame.render_widget(Clear, area);
+        ctx.frame.render_widget(
+            Paragraph::new(lines).block(ctx.style.block(false).title("Health Log")),
+            area,
+        );
+    }
+
+    /// Render the palette over the resource.
+    fn render_index_palette<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let matches: Vec<ListItem> = self
+            .index_palette_matches()
+            .into_iter()
+            .map(|index| {
+                ListItem::new(Spans::from(vec![
+                    Span::styled(
+                        format!("  {}", self.state.redactor.index(&index.index)),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" "),
+                    Span::styled(
+                        index.health.clone(),
+                        Style::default().fg(health_color(index.health.as_str())),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let palette_area = {
+            let width = ctx.rect.width * 3 / 5;
+            let height = cmp::min(
+                matches.len() as u16 + 1 + 2 * ctx.style.box_border_height(),
+                ctx.rect.height.saturating_sub(1),
+            );
+            Rect::new(
+                ctx.rect.x + (ctx.rect.width - width) / 2,
+                ctx.rect.y + 1,
+                width,
+                height,
+            )
+        };
+        let (input_area, matches_area) = {
+            let chunks = Layout::default()
+                .direction(Vertical)
+                .constraints([
+                    Constraint::Length(1 + ctx.style.box_border_height()),
+                    Constraint::Min(0),
+                ])
+                .split(palette_area);
+            (chunks[0], chunks[1])
+        };
+
+        ctx.frame.render_widget(Clear, palette_area);
+
+        let input = Paragraph::new(Text::raw(format!("{}_", self.state.index_palette_query)))
+            .block(ctx.style.block(true).title("Open Index"));
+
+        ctx.frame.render_widget(input, input_area);
+
+        let title = format!("{} matches", matches.len());
+        let matches = List::new(matches)
+            .block(ctx.style.block(false).title(title))
+            .highlight_style(ctx.style.highlight_style())
+            .highlight_symbol(">");
+
+        ctx.frame.render_stateful_widget(
+            matches,
+            matches_area,
+            &mut self.state.index_palette_list_state,
+        );
+    }
+
+    fn stale_since(&self, resource: ElasticsearchResourceKind) -> Option<Duration> {
+        self.selected_cluster_name()
+            .and_then(|name| self.data.stale_since(name, resource))
+    }
+
+    /// Append when the data was last fetched if the refresh of the resource failed.
+    fn annotate_stale<'a>(
+        &self,
+        mut title: Spans<'a>,
+        resource: ElasticsearchResourceKind,
+    ) -> Spans<'a> {
+        if let Some(elapsed) = self.stale_since(resource) {
+            title.0.push(Span::styled(
+                format!(" (stale, last ok {} ago)", humanize_duration(elapsed)),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        title
+    }
+
+    fn render_left<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let (cluster_list_area, resource_list_area) = {
             let chunks = Layout::default()
                 .direction(Vertical)
                 .constraints([
@@ -257,16 +2061,28 @@ impl ElasticsearchComponent {
             (chunks[0], chunks[1])
         };
 
+        let cluster_title = match self.state.cluster_sort {
+            ClusterSort::Config => "Cluster",
+            ClusterSort::Name => "Cluster [name]",
+            ClusterSort::Health => "Cluster [health]",
+        };
         let cluster_list: Vec<ListItem> = self
             .cluster_names()
             .enumerate()
             .map(|(idx, name)| {
+                let mut style = Style::default().add_modifier(
+                    ctx.style
+                        .selected_item_modifier(idx, self.state.cluster_list_state.selected()),
+                );
+                if let Some(color) = self.label_colors.get(name) {
+                    style = style.fg(*color);
+                }
+                if self.build_failures.contains_key(name) {
+                    style = style.fg(Color::DarkGray);
+                }
                 ListItem::new(Text::styled(
-                    name.to_owned(),
-                    Style::default().add_modifier(
-                        ctx.style
-                            .selected_item_modifier(idx, self.state.cluster_list_state.selected()),
-                    ),
+                    self.state.redactor.cluster(name).into_owned(),
+                    style,
                 ))
             })
             .collect();
@@ -274,7 +2090,7 @@ impl ElasticsearchComponent {
             .block(
                 ctx.style
                     .block(self.state.focused == Some(ClusterList))
-                    .title(ctx.navigable_title("Cluster")),
+                    .title(ctx.navigable_title(cluster_title)),
             )
             .highlight_style(ctx.style.highlight_style())
             .highlight_symbol("> ");
@@ -294,91 +2110,1570 @@ impl ElasticsearchComponent {
             })
             .collect();
 
-        let resource_list = List::new(resource_list)
+        let resource_list = List::new(resource_list)
+            .block(
+                ctx.style
+                    .block(self.state.focused == Some(ResourceList))
+                    .title(ctx.navigable_title("Elasticsearch")),
+            )
+            .highlight_style(ctx.style.highlight_style())
+            .highlight_symbol("> ");
+
+        ctx.frame.render_stateful_widget(
+            cluster_list,
+            cluster_list_area,
+            &mut self.state.cluster_list_state,
+        );
+
+        ctx.frame.render_stateful_widget(
+            resource_list,
+            resource_list_area,
+            &mut self.state.resource_list_state,
+        );
+    }
+
+    /// Render why the resource is not shown yet.
+    fn render_fetch_status<B>(&self, ctx: &mut ViewContext<B>, resource: ElasticsearchResourceKind)
+    where
+        B: tui::backend::Backend,
+    {
+        let status = self
+            .selected_cluster_name()
+            .map(|name| self.data.get_fetch_status(name, resource))
+            .unwrap_or_default();
+        let text = match status {
+            FetchStatus::NotRequested | FetchStatus::InFlight => Text::raw("loading…"),
+            FetchStatus::Ok => Text::raw("no data"),
+            FetchStatus::Err(reason) => {
+                Text::styled(format!("error: {reason}"), Style::default().fg(Color::Red))
+            }
+        };
+
+        ctx.frame
+            .render_widget(Paragraph::new(text).wrap(Wrap { trim: false }), ctx.rect);
+    }
+
+    fn render_cluster<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        if let Some(health) = self
+            .selected_cluster_name()
+            .and_then(|name| self.data.get_cluster_health(name))
+        {
+            // wide enough to show two columns of key values side by side.
+            const TWO_COLUMN_MIN_WIDTH: u16 = 100;
+
+            let mut cluster_health =
+                ClusterHealthFormatter(health, ctx.style, &self.thousands_separator).spans();
+            if self.state.redactor.is_enabled() {
+                // the first line is the cluster name.
+                let name = self.selected_cluster_name().unwrap_or_default();
+                cluster_health[0] = ctx
+                    .style
+                    .key_value_spans("cluster_name", self.state.redactor.cluster(name));
+            }
+            let columns: Vec<Vec<Spans>> = if ctx.rect.width >= TWO_COLUMN_MIN_WIDTH {
+                let half = cluster_health.len().div_ceil(2);
+                cluster_health
+                    .chunks(half)
+                    .map(|column| column.to_vec())
+                    .collect()
+            } else {
+                vec![cluster_health]
+            };
+            let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+            // status timeline and the sparkline of active shards percent.
+            let follow_height = if self.state.follow {
+                1 + 4 + ctx.style.box_border_height()
+            } else {
+                0
+            };
+
+            let (cluster_health_area, follow_area, allocation_detail_area) = {
+                let chunks = Layout::default()
+                    .direction(Vertical)
+                    .constraints([
+                        Constraint::Length(height as u16 + ctx.style.box_border_height()),
+                        Constraint::Length(follow_height),
+                        Constraint::Percentage(100),
+                    ])
+                    .split(ctx.rect);
+                (chunks[0], chunks[1], chunks[2])
+            };
+
+            let block = ctx
+                .style
+                .block(false)
+                .title(self.annotate_stale(Spans::from("Cluster Health"), Cluster));
+            let column_areas = Layout::default()
+                .direction(Horizontal)
+                .constraints(vec![
+                    Constraint::Ratio(1, columns.len() as u32);
+                    columns.len()
+                ])
+                .split(block.inner(cluster_health_area));
+
+            ctx.frame.render_widget(block, cluster_health_area);
+            for (column, area) in columns.into_iter().zip(column_areas) {
+                let column = Paragraph::new(column).alignment(Alignment::Left);
+
+                ctx.frame.render_widget(column, area);
+            }
+
+            if self.state.follow {
+                self.render_health_history(ctx.with(follow_area));
+            }
+            if self.state.entered == Some(AllocationDetail) {
+                self.render_allocation_detail(ctx.with(allocation_detail_area));
+            }
+        } else {
+            self.render_fetch_status(ctx, Cluster);
+        }
+    }
+
+    /// Timeline of the status and the active shards percent of the latest samples.
+    fn render_health_history<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let history = match self
+            .selected_cluster_name()
+            .and_then(|name| self.data.get_health_history(name))
+        {
+            Some(history) => history,
+            None => return,
+        };
+
+        let block = ctx.style.block(false).title("Follow");
+        let inner = block.inner(ctx.rect);
+        // as many samples as columns, the latest on the right.
+        let samples = history
+            .iter()
+            .skip(history.len().saturating_sub(inner.width as usize));
+
+        let (timeline_area, sparkline_area) = {
+            let chunks = Layout::default()
+                .direction(Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(inner);
+            (chunks[0], chunks[1])
+        };
+
+        let timeline: Vec<Span> = samples
+            .clone()
+            .map(|sample| {
+                Span::styled(
+                    "█",
+                    Style::default().fg(health_color(sample.status.as_str())),
+                )
+            })
+            .collect();
+        let percents: Vec<u64> = samples
+            .map(|sample| sample.active_shards_percent.round() as u64)
+            .collect();
+        let sparkline = Sparkline::default()
+            .data(&percents)
+            .max(100)
+            .style(Style::default().fg(Color::Cyan));
+
+        ctx.frame.render_widget(block, ctx.rect);
+        ctx.frame
+            .render_widget(Paragraph::new(Spans::from(timeline)), timeline_area);
+        ctx.frame.render_widget(sparkline, sparkline_area);
+    }
+
+    fn render_allocation_detail<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let text: Text = match self
+            .selected_cluster_name()
+            .and_then(|name| self.data.get_allocation_explain(name))
+        {
+            Some(Some(explain)) => {
+                let mut lines = vec![
+                    ctx.style
+                        .key_value_spans("index", self.state.redactor.index(&explain.index)),
+                    ctx.style.key_value_spans("shard", explain.shard),
+                    ctx.style.key_value_spans("primary", explain.primary),
+                    ctx.style
+                        .key_value_spans("current_state", &explain.current_state),
+                ];
+                if let Some(info) = explain.unassigned_info.as_ref() {
+                    lines.push(ctx.style.key_value_spans_with_style(
+                        "reason",
+                        &info.reason,
+                        Style::default().fg(Color::Red),
+                    ));
+                    lines.push(ctx.style.key_value_spans("unassigned_at", &info.at));
+                    if let Some(status) = info.last_allocation_status.as_ref() {
+                        lines.push(ctx.style.key_value_spans("last_allocation_status", status));
+                    }
+                    if let Some(details) = info.details.as_ref() {
+                        lines.push(ctx.style.key_value_spans("details", details));
+                    }
+                }
+                if let Some(explanation) = explain.explanation.as_ref() {
+                    lines.push(ctx.style.key_value_spans("explanation", explanation));
+                }
+                for node in explain.node_allocation_decisions.iter() {
+                    lines.push(Spans::default());
+                    lines.push(
+                        ctx.style
+                            .key_value_spans(&node.node_name, &node.node_decision),
+                    );
+                    lines.extend(node.deciders.iter().map(|decider| {
+                        Spans::from(vec![
+                            Span::styled(
+                                format!("  {} {}: ", decider.decider, decider.decision),
+                                Style::default().add_modifier(Modifier::DIM),
+                            ),
+                            Span::raw(decider.explanation.clone()),
+                        ])
+                    }));
+                }
+                Text::from(lines)
+            }
+            Some(None) => Text::styled("all shards assigned", Style::default().fg(Color::Green)),
+            None => Text::raw("loading…"),
+        };
+
+        let detail = Paragraph::new(text)
+            .block(
+                ctx.style
+                    .block(self.state.focused == Some(ClusterList))
+                    .title("Allocation Explain"),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((self.state.allocation_detail_scroll, 0));
+
+        ctx.frame.render_widget(detail, ctx.rect);
+    }
+
+    fn render_index<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let preset = self
+            .state
+            .index_filter_preset
+            .and_then(|i| self.index_filter_presets.get(i));
+
+        self.state.index_table_width = Some(ctx.rect.width);
+
+        if let Some(indices) = self.selected_cluster_name().and_then(|name| {
+            self.data.get_visible_indices(
+                name,
+                preset.map(|(_, f)| f),
+                self.state.show_hidden_indices,
+            )
+        }) {
+            let mut indices: Vec<&CatIndex> = indices.collect();
+            sort_indices(&mut indices, self.state.index_sort);
+            if indices.is_empty() {
+                ctx.frame
+                    .render_widget(Paragraph::new(Text::raw("no data")), ctx.rect);
+                return;
+            }
+
+            // changes since the previous fetch, the disappeared ones are kept at the bottom.
+            let diff = self
+                .selected_cluster_name()
+                .and_then(|name| self.data.get_index_diff(name));
+            let disappeared: Vec<&CatIndex> = self
+                .selected_cluster_name()
+                .map(|name| {
+                    self.data
+                        .get_disappeared_indices(
+                            name,
+                            preset.map(|(_, f)| f),
+                            self.state.show_hidden_indices,
+                        )
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let index_rows = index_rows(
+                &indices,
+                self.state.group_indices,
+                self.index_group_pattern.as_ref(),
+                self.state.expanded_index_group.as_deref(),
+            );
+
+            let num_index = index_rows.len() + disappeared.len();
+            let expanded = self.state.expanded_index.as_deref().and_then(|name| {
+                let cluster = self.selected_cluster_name()?;
+                let index = indices.iter().find(|index| index.index == name)?;
+                Some((name, self.expanded_index_lines(ctx.style, cluster, index)))
+            });
+            let expanded_height = expanded.as_ref().map_or(0, |(_, lines)| lines.len()) as u16;
+
+            let wide = self.state.wide_rows;
+            let row_height = if wide { 2 } else { 1 };
+            let columns = self.visible_index_columns();
+            let active_column =
+                (self.state.focused == Some(IndexTable)).then_some(self.state.index_active_column);
+
+            let max_index_width = index_rows
+                .iter()
+                .map(|row| match row {
+                    IndexRow::Index(index) => index.index.len() + 2,
+                    IndexRow::Member(index) => index.index.len() + 4,
+                    // like "▸ logs* (12)".
+                    IndexRow::Group { prefix, members } => {
+                        prefix.len() + members.len().to_string().len() + 6
+                    }
+                })
+                .max()
+                .unwrap_or(10);
+            // the expanded lines are in the first column, widen it to show them in full.
+            let expanded_width = expanded
+                .iter()
+                .flat_map(|(_, lines)| lines.iter().map(Spans::width))
+                .max()
+                .unwrap_or(0);
+
+            let warnings = self
+                .selected_cluster_name()
+                .and_then(|name| self.configs.iter().find(|c| c.name == name))
+                .and_then(|c| c.index_warnings.clone())
+                .unwrap_or_default();
+
+            let scope = self.state.size_scope;
+            let byte_display = self.state.byte_display;
+            // room for the raw bytes after the humanized size.
+            let bytes_width = match byte_display {
+                ByteDisplay::Both => 11,
+                ByteDisplay::Humanized | ByteDisplay::Raw => 0,
+            };
+            let docs_header = format!("DocsCount ({})", scope.label());
+            let store_header = format!("StoreSize ({})", scope.label());
+            let mut default_widths = HashMap::new();
+            let (header, column_constraints): (Vec<_>, Vec<_>) = columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    let (header, width) = match column {
+                        IndexColumn::Index => ("  Index", max_index_width),
+                        IndexColumn::Health => ("Health", 6),
+                        IndexColumn::Status => ("Status", 6),
+                        IndexColumn::Primary => ("Primary", 7),
+                        IndexColumn::Replica => ("Replica", 7),
+                        IndexColumn::Shards => ("Shards", 7),
+                        IndexColumn::DocsCount => (docs_header.as_str(), 17),
+                        IndexColumn::DocsDeleted => ("DocsDeleted", 12),
+                        IndexColumn::StoreSize => (store_header.as_str(), 17 + bytes_width),
+                        IndexColumn::PrimaryStoreSize => ("PrimaryStoreSize", 18 + bytes_width),
+                        IndexColumn::Uuid => ("Uuid", 22),
+                    };
+                    default_widths.insert(*column, width as u16);
+                    let width = match self.state.index_column_widths.get(column) {
+                        Some(delta) => (width as i16 + delta)
+                            .clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH)
+                            as usize,
+                        None => width,
+                    };
+                    let width = if i == 0 {
+                        width.max(expanded_width)
+                    } else {
+                        width
+                    };
+                    let mut style = Style::default().add_modifier(Modifier::DIM | Modifier::BOLD);
+                    if active_column == Some(i) {
+                        style = style.add_modifier(Modifier::UNDERLINED);
+                    }
+                    (
+                        Cell::from(header).style(style),
+                        Constraint::Length(width as u16),
+                    )
+                })
+                .unzip();
+            self.state.index_column_default_widths = default_widths;
+
+            let header = Row::new(header).height(1).bottom_margin(0);
+
+            let mut title = match preset {
+                Some((name, _)) => format!("Index [{name}]"),
+                None => "Index".to_owned(),
+            };
+            match self.state.index_sort {
+                IndexSort::Name => (),
+                IndexSort::Health => title.push_str(" (health)"),
+                IndexSort::StoreSize => title.push_str(" (store size)"),
+            }
+            if self.state.group_indices {
+                title.push_str(" (grouped)");
+            }
+            if !self.state.show_hidden_indices {
+                let hidden = self
+                    .selected_cluster_name()
+                    .map(|name| self.data.count_hidden_indices(name, preset.map(|(_, f)| f)))
+                    .unwrap_or(0);
+                if hidden > 0 {
+                    title.push_str(&format!(" ({hidden} hidden)"));
+                }
+            }
+
+            // totals of the visible indices, so they follow the filter.
+            let summary = IndexSummary::new(&indices, scope).spans(
+                ctx.style,
+                self.byte_units,
+                &self.thousands_separator,
+                scope,
+            );
+
+            // the group rows show the sums of their indices, which follow the size scope.
+            let summaries: Vec<Option<CatIndex>> = index_rows
+                .iter()
+                .map(|row| match row {
+                    IndexRow::Group { prefix, members } => {
+                        Some(summarize_indices(prefix, members, scope))
+                    }
+                    _ => None,
+                })
+                .collect();
+            let expanded_group = self.state.expanded_index_group.as_deref();
+            let rows = index_rows
+                .iter()
+                .zip(summaries.iter())
+                .filter_map(|(row, summary)| match row {
+                    IndexRow::Index(index) => Some((
+                        *index,
+                        diff.and_then(|diff| diff.change(&index.index)),
+                        "  ",
+                    )),
+                    IndexRow::Member(index) => Some((
+                        *index,
+                        diff.and_then(|diff| diff.change(&index.index)),
+                        "    ",
+                    )),
+                    IndexRow::Group { prefix, .. } => {
+                        let marker = if expanded_group == Some(*prefix) {
+                            "▾ "
+                        } else {
+                            "▸ "
+                        };
+                        summary.as_ref().map(|summary| (summary, None, marker))
+                    }
+                })
+                .chain(
+                    disappeared
+                        .iter()
+                        .map(|index| (*index, Some(IndexChange::Disappeared), "  ")),
+                );
+            let rows = rows.map(|(index, change, marker)| {
+                // closed indices may miss them.
+                let pri_store_bytes = index.pri_store_size.as_deref().unwrap_or_default();
+                let pri_store_size = match index.pri_store_size.as_deref() {
+                    Some(size) => format_str_bytes(size, self.byte_units, byte_display),
+                    None => "-".to_owned(),
+                };
+                let uuid = index.uuid.as_deref().unwrap_or("-");
+                let store_bytes = scope.store_size(index);
+                let store_size = match store_bytes {
+                    "" => "-".to_owned(),
+                    bytes => format_str_bytes(bytes, self.byte_units, byte_display),
+                };
+                let pri_warned = warnings.too_many_primaries(&index.pri, pri_store_bytes);
+                let rep_warned = warnings.missing_replicas(&index.rep);
+                let mut cells: Vec<Text> = columns
+                    .iter()
+                    .map(|column| {
+                        let mut cell = Text::from(match column {
+                            IndexColumn::Index => Span::styled(
+                                marker.to_owned() + &self.state.redactor.index(&index.index),
+                                Style::default().add_modifier(Modifier::BOLD),
+                            ),
+                            IndexColumn::Health => Span::styled(
+                                index.health.as_str(),
+                                Style::default().fg(health_color(index.health.as_str())),
+                            ),
+                            IndexColumn::Status => {
+                                Span::styled(index.status.as_str(), Style::default())
+                            }
+                            IndexColumn::Primary => {
+                                shard_count_span(index.pri.clone(), pri_warned, warnings.glyph)
+                            }
+                            IndexColumn::Replica => {
+                                shard_count_span(index.rep.clone(), rep_warned, warnings.glyph)
+                            }
+                            IndexColumn::Shards => shard_count_span(
+                                format!("{}/{}", index.pri, index.rep),
+                                pri_warned || rep_warned,
+                                warnings.glyph,
+                            ),
+                            IndexColumn::DocsCount => Span::styled(
+                                format_str_count(
+                                    &scope.docs_count(index),
+                                    &self.thousands_separator,
+                                ),
+                                Style::default().fg(Color::Cyan),
+                            ),
+                            IndexColumn::DocsDeleted => Span::styled(
+                                format_str_count(&index.docs_deleted, &self.thousands_separator),
+                                ctx.style
+                                    .deleted_docs_style(&index.docs_count, &index.docs_deleted),
+                            ),
+                            IndexColumn::StoreSize => Span::styled(
+                                store_size.clone(),
+                                ctx.style.store_size_style(store_bytes),
+                            ),
+                            IndexColumn::PrimaryStoreSize => Span::styled(
+                                pri_store_size.clone(),
+                                ctx.style.store_size_style(pri_store_bytes),
+                            ),
+                            IndexColumn::Uuid => Span::styled(uuid, Style::default()),
+                        });
+                        // below the index name and the store size.
+                        match column {
+                            IndexColumn::Index if wide => {
+                                cell.lines.push(Spans::from(Span::styled(
+                                    format!("    {uuid}"),
+                                    Style::default().add_modifier(Modifier::DIM),
+                                )))
+                            }
+                            IndexColumn::StoreSize if wide => {
+                                cell.lines.push(Spans::from(Span::styled(
+                                    format!("pri {pri_store_size}"),
+                                    ctx.style.store_size_style(pri_store_bytes),
+                                )))
+                            }
+                            _ => (),
+                        }
+                        cell
+                    })
+                    .collect();
+                let mut height = row_height;
+                if let Some((_, lines)) = expanded.as_ref().filter(|(name, _)| *name == index.index)
+                {
+                    if let Some(first) = cells.first_mut() {
+                        first.lines.extend(lines.iter().cloned());
+                    }
+                    height += lines.len() as u16;
+                }
+                let style = match change {
+                    Some(IndexChange::Appeared) => Style::default().fg(Color::Green),
+                    Some(IndexChange::Changed) => Style::default().bg(Color::DarkGray),
+                    Some(IndexChange::Disappeared) => Style::default()
+                        .fg(Color::Red)
+                        .add_modifier(Modifier::CROSSED_OUT),
+                    None => Style::default(),
+                };
+                Row::new(cells.into_iter().map(Cell::from))
+                    .height(height)
+                    .style(style)
+            });
+
+            let (summary_area, indices_area) = {
+                let chunks = Layout::default()
+                    .direction(Vertical)
+                    .constraints([
+                        Constraint::Length(1),
+                        Constraint::Length(
+                            num_index as u16 * row_height
+                                + expanded_height
+                                + 1 // header
+                                + ctx.style.box_border_height(),
+                        ),
+                        Constraint::Percentage(100),
+                    ])
+                    .split(ctx.rect);
+                (chunks[0], chunks[1])
+            };
+
+            ctx.frame
+                .render_widget(Paragraph::new(summary), summary_area);
+
+            let indices = Table::new(rows)
+                .header(header)
+                .block(
+                    ctx.style
+                        .block(self.state.focused == Some(IndexTable))
+                        .title(self.annotate_stale(ctx.navigable_title(&title), Index)),
+                )
+                .highlight_style(ctx.style.highlight_style())
+                .highlight_symbol(">")
+                .widths(column_constraints.as_slice());
+
+            ctx.frame.render_stateful_widget(
+                indices,
+                indices_area,
+                &mut self.state.index_table_state,
+            );
+        } else {
+            self.render_fetch_status(ctx, Index);
+        }
+    }
+
+    /// Detail shown under the expanded row of the index table.
+    fn expanded_index_lines(
+        &self,
+        style: &Styled,
+        cluster: &str,
+        index: &CatIndex,
+    ) -> Vec<Spans<'static>> {
+        let bytes = |size: Option<&str>| match size {
+            // closed indices have no size.
+            Some("") | None => "-".to_owned(),
+            Some(size) => format!(
+                "{} bytes",
+                format_str_count(size, &self.thousands_separator)
+            ),
+        };
+        let mappings = match self.data.get_index_mappings(cluster, &index.index) {
+            Some(mappings) => format!(
+                "{} fields",
+                format_count(
+                    count_mapping_fields(mappings) as i128,
+                    &self.thousands_separator
+                )
+            ),
+            None => "loading…".to_owned(),
+        };
+        vec![
+            style.key_value_spans("  uuid", index.uuid.as_deref().unwrap_or("-")),
+            style.key_value_spans(
+                "  docs_deleted",
+                format_str_count(&index.docs_deleted, &self.thousands_separator),
+            ),
+            style.key_value_spans("  store_size", bytes(Some(&index.store_size))),
+            style.key_value_spans(
+                "  primary_store_size",
+                bytes(index.pri_store_size.as_deref()),
+            ),
+            style.key_value_spans("  mappings", mappings),
+        ]
+    }
+
+    fn render_index_detail<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        const SAMPLE_DOC_MAX_LINES: usize = 10;
+
+        let (cluster, index) = match self.selected_cluster_name().zip(self.selected_index()) {
+            Some(selected) => selected,
+            None => {
+                let not_found = Paragraph::new(Text::raw("not found"));
+
+                ctx.frame.render_widget(not_found, ctx.rect);
+                return;
+            }
+        };
+
+        let summary = vec![
+            ctx.style.key_value_spans("health", &index.health),
+            ctx.style.key_value_spans("status", &index.status),
+            ctx.style.key_value_spans("primary", &index.pri),
+            ctx.style.key_value_spans("replica", &index.rep),
+            ctx.style.key_value_spans(
+                "docs_count",
+                format_str_count(&index.docs_count, &self.thousands_separator),
+            ),
+            ctx.style.key_value_spans(
+                "docs_deleted",
+                format_str_count(&index.docs_deleted, &self.thousands_separator),
+            ),
+            ctx.style.key_value_spans(
+                "store_size",
+                format_str_bytes(&index.store_size, self.byte_units, self.state.byte_display),
+            ),
+            ctx.style.key_value_spans(
+                "primary_store_size",
+                index
+                    .pri_store_size
+                    .as_deref()
+                    .map(|size| format_str_bytes(size, self.byte_units, self.state.byte_display))
+                    .unwrap_or_else(|| "-".to_owned()),
+            ),
+            ctx.style
+                .key_value_spans("uuid", index.uuid.as_deref().unwrap_or("-")),
+        ];
+
+        let settings = match self.data.get_index_settings(cluster, &index.index) {
+            Some(settings) => {
+                let mut lines = vec![
+                    ctx.style
+                        .key_value_spans("number_of_shards", &settings.number_of_shards),
+                    ctx.style
+                        .key_value_spans("number_of_replicas", &settings.number_of_replicas),
+                    ctx.style.key_value_spans("uuid", &settings.uuid),
+                    ctx.style.key_value_spans(
+                        "provided_name",
+                        settings.provided_name.as_deref().unwrap_or("-"),
+                    ),
+                    ctx.style.key_value_spans(
+                        "creation_date",
+                        self.timestamp_format()
+                            .epoch_millis(&settings.creation_date),
+                    ),
+                ];
+                if let Some(analyzers) = settings
+                    .analysis
+                    .as_ref()
+                    .and_then(|analysis| analysis.analyzer.as_ref())
+                {
+                    let names: Vec<&str> = analyzers.keys().map(String::as_str).collect();
+                    lines.push(ctx.style.key_value_spans("analyzers", names.join(", ")));
+                }
+                lines
+            }
+            None => vec![Spans::from("not found")],
+        };
+
+        let (summary_area, settings_area, sample_docs_area) = {
+            let chunks = Layout::default()
+                .direction(Vertical)
+                .constraints([
+                    Constraint::Length(summary.len() as u16 + ctx.style.box_border_height()),
+                    Constraint::Length(settings.len() as u16 + ctx.style.box_border_height()),
+                    Constraint::Percentage(100),
+                ])
+                .split(ctx.rect);
+            (chunks[0], chunks[1], chunks[2])
+        };
+
+        let summary = Paragraph::new(summary)
+            .block(
+                ctx.style
+                    .block(false)
+                    .title(self.state.redactor.index(&index.index).into_owned()),
+            )
+            .alignment(Alignment::Left);
+
+        ctx.frame.render_widget(summary, summary_area);
+
+        let settings = Paragraph::new(settings)
+            .block(ctx.style.block(false).title("Settings"))
+            .alignment(Alignment::Left);
+
+        ctx.frame.render_widget(settings, settings_area);
+
+        let sample_docs: Text = if index.status == "close" {
+            Text::raw("index is closed")
+        } else if let Some(docs) = self.data.get_sample_docs(cluster, &index.index) {
+            let mut lines: Vec<Spans> = Vec::new();
+            for hit in docs.hits.hits.iter() {
+                lines.push(ctx.style.key_value_spans("_id", &hit.id));
+                let source = hit
+                    .source
+                    .as_ref()
+                    .map(|source| format_json(source, self.state.compact_json))
+                    .unwrap_or_else(|| "_source disabled".to_owned());
+                lines.extend(
+                    truncate_lines(&source, SAMPLE_DOC_MAX_LINES)
+                        .into_iter()
+                        .map(Spans::from),
+                );
+            }
+            Text::from(lines)
+        } else {
+            Text::raw("not found")
+        };
+
+        let title = match self
+            .data
+            .get_sample_docs(cluster, &index.index)
+            .filter(|_| index.status != "close")
+        {
+            Some(docs) => {
+                let total = match &docs.hits.total {
+                    Some(total) if total.relation == "gte" => format!(
+                        "{}+",
+                        format_count(total.value as i128, &self.thousands_separator)
+                    ),
+                    Some(total) => format_count(total.value as i128, &self.thousands_separator),
+                    None => "?".to_owned(),
+                };
+                format!(
+                    "Sample Documents (showing {} of {total} hits)",
+                    format_count(docs.hits.hits.len() as i128, &self.thousands_separator)
+                )
+            }
+            None => "Sample Documents".to_owned(),
+        };
+        let sample_docs = Paragraph::new(sample_docs)
+            .block(
+                ctx.style
+                    .block(self.state.focused == Some(IndexTable))
+                    .title(title),
+            )
+            .scroll((self.state.index_detail_scroll, 0));
+
+        ctx.frame.render_widget(sample_docs, sample_docs_area);
+    }
+
+    fn render_dashboard<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let names: Vec<&str> = self.cluster_names().collect();
+        if names.is_empty() {
+            ctx.frame
+                .render_widget(Paragraph::new(Text::raw("no cluster")), ctx.rect);
+            return;
+        }
+        let max_name_width = names.iter().map(|n| n.len() + 2).max().unwrap_or(10);
+
+        let (header, column_constraints): (Vec<_>, Vec<_>) = [
+            ("  Cluster", Constraint::Length(max_name_width as u16)),
+            ("Status", Constraint::Length(7)),
+            ("Nodes", Constraint::Length(6)),
+            ("Unassigned", Constraint::Length(10)),
+            ("", Constraint::Min(10)),
+        ]
+        .into_iter()
+        .map(|(h, c)| {
+            (
+                Cell::from(h).style(Style::default().add_modifier(Modifier::DIM | Modifier::BOLD)),
+                c,
+            )
+        })
+        .unzip();
+
+        let header = Row::new(header).height(1).bottom_margin(0);
+
+        let rows = names.iter().map(|name| {
+            let mut cells = vec![Span::styled(
+                format!("  {}", self.state.redactor.cluster(name)),
+                match self.label_colors.get(*name) {
+                    Some(color) => Style::default().fg(*color).add_modifier(Modifier::BOLD),
+                    None => Style::default().add_modifier(Modifier::BOLD),
+                },
+            )];
+            match self.data.get_cluster_health(name) {
+                Some(health) => cells.extend([
+                    Span::styled(
+                        health.status.clone(),
+                        Style::default().fg(health_color(health.status.as_str())),
+                    ),
+                    Span::raw(health.number_of_nodes.to_string()),
+                    Span::styled(
+                        health.unassigned_shards.to_string(),
+                        if health.unassigned_shards > 0 {
+                            Style::default().fg(Color::Yellow)
+                        } else {
+                            Style::default()
+                        },
+                    ),
+                ]),
+                // the reason is shown in the last column.
+                None => {
+                    cells.extend([Span::raw("-"), Span::raw("-"), Span::raw("-")]);
+                    cells.push(match self.data.get_fetch_status(name, Cluster) {
+                        FetchStatus::Err(reason) => Span::styled(
+                            format!("error: {reason}"),
+                            Style::default().fg(Color::Red),
+                        ),
+                        _ => Span::raw("loading…"),
+                    })
+                }
+            }
+            Row::new(cells.into_iter().map(Cell::from)).height(1)
+        });
+
+        let dashboard_area = {
+            Layout::default()
+                .direction(Vertical)
+                .constraints([
+                    Constraint::Length(names.len() as u16 + 1 + ctx.style.box_border_height()),
+                    Constraint::Percentage(100),
+                ])
+                .split(ctx.rect)[0]
+        };
+
+        let dashboard = Table::new(rows)
+            .header(header)
+            .block(
+                ctx.style
+                    .block(self.state.focused == Some(DashboardTable))
+                    .title(ctx.navigable_title("Dashboard")),
+            )
+            .highlight_style(ctx.style.highlight_style())
+            .highlight_symbol(">")
+            .widths(column_constraints.as_slice());
+
+        ctx.frame.render_stateful_widget(
+            dashboard,
+            dashboard_area,
+            &mut self.state.dashboard_table_state,
+        );
+    }
+
+    fn render_nodes<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        if let Some(nodes) = self
+            .selected_cluster_name()
+            .and_then(|name| self.data.get_nodes(name))
+        {
+            if nodes.is_empty() {
+                ctx.frame
+                    .render_widget(Paragraph::new(Text::raw("no data")), ctx.rect);
+                return;
+            }
+
+            let max_name_width = nodes.iter().map(|n| n.name.len() + 2).max().unwrap_or(10);
+
+            let (header, column_constraints): (Vec<_>, Vec<_>) = [
+                ("  Name", Constraint::Length(max_name_width as u16)),
+                ("Ip", Constraint::Length(16)),
+                ("Role", Constraint::Length(12)),
+                ("Master", Constraint::Length(6)),
+                ("Heap%", Constraint::Length(6)),
+                ("Ram%", Constraint::Length(6)),
+                ("Cpu%", Constraint::Length(6)),
+                ("Load1m", Constraint::Length(7)),
+                ("Disk%", Constraint::Length(6)),
+            ]
+            .into_iter()
+            .map(|(h, c)| {
+                (
+                    Cell::from(h)
+                        .style(Style::default().add_modifier(Modifier::DIM | Modifier::BOLD)),
+                    c,
+                )
+            })
+            .unzip();
+
+            let header = Row::new(header).height(1).bottom_margin(0);
+
+            let rows = nodes.iter().map(|node| {
+                let metric = |value: &Option<String>| value.clone().unwrap_or_default();
+                let cells = vec![
+                    Span::styled(
+                        format!("  {}", node.name),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(node.ip.as_str(), Style::default()),
+                    Span::styled(node.node_role.as_str(), Style::default()),
+                    Span::styled(node.master.as_str(), Style::default()),
+                    Span::styled(
+                        metric(&node.heap_percent),
+                        ctx.style
+                            .node_memory_style(node.heap_percent.as_deref().unwrap_or_default()),
+                    ),
+                    Span::styled(
+                        metric(&node.ram_percent),
+                        ctx.style
+                            .node_memory_style(node.ram_percent.as_deref().unwrap_or_default()),
+                    ),
+                    Span::styled(metric(&node.cpu), Style::default()),
+                    Span::styled(metric(&node.load_1m), Style::default()),
+                    Span::styled(metric(&node.disk_used_percent), Style::default()),
+                ]
+                .into_iter()
+                .map(Cell::from);
+                Row::new(cells).height(1)
+            });
+
+            let nodes_area = {
+                Layout::default()
+                    .direction(Vertical)
+                    .constraints([
+                        Constraint::Length(nodes.len() as u16 + 1 + ctx.style.box_border_height()),
+                        Constraint::Percentage(100),
+                    ])
+                    .split(ctx.rect)[0]
+            };
+
+            let nodes = Table::new(rows)
+                .header(header)
+                .block(
+                    ctx.style
+                        .block(self.state.focused == Some(NodeTable))
+                        .title(self.annotate_stale(ctx.navigable_title("Node"), Node)),
+                )
+                .highlight_style(ctx.style.highlight_style())
+                .highlight_symbol(">")
+                .widths(column_constraints.as_slice());
+
+            ctx.frame
+                .render_stateful_widget(nodes, nodes_area, &mut self.state.node_table_state);
+        } else {
+            self.render_fetch_status(ctx, Node);
+        }
+    }
+
+    fn render_tasks<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        if let Some(tasks) = self
+            .selected_cluster_name()
+            .and_then(|name| self.data.get_tasks(name))
+        {
+            if tasks.is_empty() {
+                ctx.frame
+                    .render_widget(Paragraph::new(Text::raw("no running tasks")), ctx.rect);
+                return;
+            }
+
+            let max_action_width = tasks.iter().map(|t| t.action.len() + 2).max().unwrap_or(10);
+
+            let (header, column_constraints): (Vec<_>, Vec<_>) = [
+                ("  Action", Constraint::Length(max_action_width as u16)),
+                ("Running", Constraint::Length(8)),
+                ("Children", Constraint::Length(8)),
+                ("Cancellable", Constraint::Length(11)),
+                ("Task Id", Constraint::Length(32)),
+                ("Description", Constraint::Percentage(100)),
+            ]
+            .into_iter()
+            .map(|(h, c)| {
+                (
+                    Cell::from(h)
+                        .style(Style::default().add_modifier(Modifier::DIM | Modifier::BOLD)),
+                    c,
+                )
+            })
+            .unzip();
+
+            let header = Row::new(header).height(1).bottom_margin(0);
+
+            let rows = tasks.iter().map(|task| {
+                let cells = vec![
+                    Span::styled(
+                        format!("  {}", task.action),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        humanize_duration(Duration::from_nanos(task.running_time_in_nanos)),
+                        Style::default(),
+                    ),
+                    Span::styled(task.children.len().to_string(), Style::default()),
+                    Span::styled(task.cancellable.to_string(), Style::default()),
+                    Span::styled(format!("{}:{}", task.node, task.id), Style::default()),
+                    Span::styled(
+                        task.description.clone().unwrap_or_default(),
+                        Style::default().add_modifier(Modifier::DIM),
+                    ),
+                ]
+                .into_iter()
+                .map(Cell::from);
+                Row::new(cells).height(1)
+            });
+
+            let tasks_area = {
+                Layout::default()
+                    .direction(Vertical)
+                    .constraints([
+                        Constraint::Length(tasks.len() as u16 + 1 + ctx.style.box_border_height()),
+                        Constraint::Percentage(100),
+                    ])
+                    .split(ctx.rect)[0]
+            };
+
+            let tasks = Table::new(rows)
+                .header(header)
+                .block(
+                    ctx.style
+                        .block(self.state.focused == Some(TaskTable))
+                        .title(
+                            self.annotate_stale(ctx.navigable_title_with_key("Task", 'T'), Task),
+                        ),
+                )
+                .highlight_style(ctx.style.highlight_style())
+                .highlight_symbol(">")
+                .widths(column_constraints.as_slice());
+
+            ctx.frame
+                .render_stateful_widget(tasks, tasks_area, &mut self.state.task_table_state);
+        } else {
+            self.render_fetch_status(ctx, Task);
+        }
+    }
+
+    fn alias_rows(&self, cluster_name: &str) -> Option<Vec<AliasRow<'_>>> {
+        self.data.get_visible_aliases(cluster_name).map(|aliases| {
+            alias_rows(
+                aliases,
+                self.state.alias_sort,
+                self.group_aliases,
+                self.state.expanded_alias.as_deref(),
+            )
+        })
+    }
+
+    /// Full values of the alias which may be truncated in the table.
+    fn alias_detail_lines<'a>(&self, style: &Styled, alias: &'a CatAlias) -> Vec<Spans<'a>> {
+        let mut lines = vec![
+            style.key_value_spans("alias", &alias.alias),
+            style.key_value_spans("index", self.state.redactor.index(&alias.index)),
+            style.key_value_spans("is_write_index", &alias.is_write_index),
+            style.key_value_spans("routing_index", &alias.routing_index),
+            style.key_value_spans("routing_search", &alias.routing_search),
+        ];
+        match serde_json::from_str::<serde_json::Value>(&alias.filter) {
+            Ok(filter) if filter.is_object() => {
+                lines.push(style.key_value_spans("filter", ""));
+                lines.extend(
+                    format_json(&filter, self.state.compact_json)
+                        .lines()
+                        .map(|line| Spans::from(format!("  {line}"))),
+                );
+            }
+            _ => lines.push(style.key_value_spans("filter", &alias.filter)),
+        }
+        lines
+    }
+
+    fn render_aliases<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        // only borrow the data, the table state is updated on render.
+        if let Some(aliases) = self.selected_cluster_name().and_then(|name| {
+            self.data.get_visible_aliases(name).map(|aliases| {
+                alias_rows(
+                    aliases,
+                    self.state.alias_sort,
+                    self.group_aliases,
+                    self.state.expanded_alias.as_deref(),
+                )
+            })
+        }) {
+            if aliases.is_empty() {
+                ctx.frame
+                    .render_widget(Paragraph::new(Text::raw("no data")), ctx.rect);
+                return;
+            }
+
+            // TODO: handle too long alias name.
+            let (header, column_constraints): (Vec<_>, Vec<_>) = [
+                ("  Alias", Constraint::Percentage(30)),
+                ("Index", Constraint::Percentage(30)),
+                ("IsWrite", Constraint::Length(7)),
+                ("Filter", Constraint::Min(10)),
+                ("RoutingIndex", Constraint::Min(12)),
+                ("RoutingSearch", Constraint::Min(13)),
+            ]
+            .into_iter()
+            .map(|(h, c)| {
+                (
+                    Cell::from(h)
+                        .style(Style::default().add_modifier(Modifier::DIM | Modifier::BOLD)),
+                    c,
+                )
+            })
+            .unzip();
+
+            let header = Row::new(header).height(1).bottom_margin(0);
+
+            let rows = aliases.iter().map(|row| {
+                let cells = match row {
+                    AliasRow::Alias(alias) | AliasRow::Member(alias) => {
+                        let name = match row {
+                            AliasRow::Member(_) => {
+                                Span::styled("    └", Style::default().add_modifier(Modifier::DIM))
+                            }
+                            _ => Span::styled(
+                                format!("  {}", alias.alias.as_str()),
+                                Style::default().add_modifier(Modifier::BOLD),
+                            ),
+                        };
+                        vec![
+                            name,
+                            Span::styled(self.state.redactor.index(&alias.index), Style::default()),
+                            Span::styled(alias.is_write_index.as_str(), Style::default()),
+                            Span::styled(alias.filter.as_str(), Style::default()),
+                            Span::styled(alias.routing_index.as_str(), Style::default()),
+                            Span::styled(alias.routing_search.as_str(), Style::default()),
+                        ]
+                    }
+                    AliasRow::Group { alias, members } => {
+                        let expanded = self.state.expanded_alias.as_deref() == Some(*alias);
+                        let marker = if expanded { "▾" } else { "▸" };
+                        let has_write = members.iter().any(|m| m.is_write_index == "true");
+                        vec![
+                            Span::styled(
+                                format!("{marker} {alias}"),
+                                Style::default().add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(
+                                format!("{} indices", members.len()),
+                                Style::default().fg(Color::Cyan),
+                            ),
+                            Span::styled(if has_write { "true" } else { "-" }, Style::default()),
+                            Span::raw(""),
+                            Span::raw(""),
+                            Span::raw(""),
+                        ]
+                    }
+                }
+                .into_iter()
+                .map(Cell::from);
+                Row::new(cells).height(1)
+            });
+
+            // full values of the selected alias which may be truncated in the table.
+            let detail: Vec<Spans> = self
+                .state
+                .alias_table_state
+                .selected()
+                .and_then(|i| aliases.get(i))
+                .map(|row| match row {
+                    AliasRow::Alias(alias) | AliasRow::Member(alias) => {
+                        self.alias_detail_lines(ctx.style, alias)
+                    }
+                    AliasRow::Group { alias, members } => {
+                        let mut lines = vec![
+                            ctx.style.key_value_spans("alias", *alias),
+                            ctx.style.key_value_spans("indices", members.len()),
+                        ];
+                        lines.extend(members.iter().map(|m| {
+                            let write = if m.is_write_index == "true" {
+                                " (write)"
+                            } else {
+                                ""
+                            };
+                            Spans::from(format!("  {}{write}", self.state.redactor.index(&m.index)))
+                        }));
+                        lines
+                    }
+                })
+                .unwrap_or_default();
+
+            // the table takes the full height and scrolls to the selected row,
+            // sizing it by the row count overflows with many aliases.
+            let (aliases_area, detail_area) = {
+                let detail_height = if detail.is_empty() {
+                    0
+                } else {
+                    cmp::min(
+                        detail.len() as u16 + ctx.style.box_border_height(),
+                        ctx.rect.height / 2,
+                    )
+                };
+                let chunks = Layout::default()
+                    .direction(Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(detail_height)])
+                    .split(ctx.rect);
+                (chunks[0], chunks[1])
+            };
+
+            if !detail.is_empty() {
+                let detail = Paragraph::new(detail)
+                    .block(ctx.style.block(false).title("Alias Detail"))
+                    .wrap(Wrap { trim: false });
+
+                ctx.frame.render_widget(detail, detail_area);
+            }
+
+            let title = match self.state.alias_sort {
+                AliasSort::Alias => "Alias",
+                AliasSort::Index => "Alias [index]",
+                AliasSort::WriteFirst => "Alias [write first]",
+            };
+            let aliases = Table::new(rows)
+                .header(header)
+                .block(
+                    ctx.style
+                        .block(self.state.focused == Some(AliasTable))
+                        .title(self.annotate_stale(ctx.navigable_title(title), Alias)),
+                )
+                .highlight_style(ctx.style.highlight_style())
+                .highlight_symbol(">")
+                .widths(column_constraints.as_slice());
+
+            ctx.frame.render_stateful_widget(
+                aliases,
+                aliases_area,
+                &mut self.state.alias_table_state,
+            );
+        } else {
+            self.render_fetch_status(ctx, Alias);
+        }
+    }
+
+    fn render_templates<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        if let Some(templates) = self
+            .selected_cluster_name()
+            .and_then(|name| self.data.get_visible_templates(name))
+        {
+            let mut templates: Vec<&IndexTemplateEntry> = templates.collect();
+            templates.sort_unstable_by_key(|t| &t.name);
+
+            let num_templates = templates.len();
+
+            let (header, column_constraints): (Vec<_>, Vec<_>) = [
+                ("  Name", Constraint::Percentage(30)),
+                ("IndexPatterns", Constraint::Percentage(40)),
+                ("Priority", Constraint::Length(8)),
+                ("ComposedOf", Constraint::Min(10)),
+            ]
+            .into_iter()
+            .map(|(h, c)| {
+                (
+                    Cell::from(h)
+                        .style(Style::default().add_modifier(Modifier::DIM | Modifier::BOLD)),
+                    c,
+                )
+            })
+            .unzip();
+
+            let header = Row::new(header).height(1).bottom_margin(0);
+
+            let rows = templates.iter().map(|t| {
+                let cells = vec![
+                    Span::styled(
+                        format!("  {}", t.name.as_str()),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(t.index_template.index_patterns.join(","), Style::default()),
+                    Span::styled(
+                        t.index_template
+                            .priority
+                            .map(|p| p.to_string())
+                            .unwrap_or_default(),
+                        Style::default(),
+                    ),
+                    Span::styled(t.index_template.composed_of.join(","), Style::default()),
+                ]
+                .into_iter()
+                .map(Cell::from);
+                Row::new(cells).height(1)
+            });
+
+            let (templates_area, detail_area) = {
+                let chunks = Layout::default()
+                    .direction(Vertical)
+                    .constraints([
+                        Constraint::Length(
+                            num_templates as u16 + 1 + ctx.style.box_border_height(),
+                        ),
+                        Constraint::Percentage(100),
+                    ])
+                    .split(ctx.rect);
+                (chunks[0], chunks[1])
+            };
+
+            let selected = self
+                .state
+                .template_table_state
+                .selected()
+                .and_then(|i| templates.get(i).copied());
+
+            let table = Table::new(rows)
+                .header(header)
+                .block(
+                    ctx.style
+                        .block(self.state.focused == Some(TemplateTable))
+                        .title(self.annotate_stale(ctx.navigable_title("Template"), Template)),
+                )
+                .highlight_style(ctx.style.highlight_style())
+                .highlight_symbol(">")
+                .widths(column_constraints.as_slice());
+
+            ctx.frame.render_stateful_widget(
+                table,
+                templates_area,
+                &mut self.state.template_table_state,
+            );
+
+            if let Some(selected) = selected {
+                let body = selected
+                    .index_template
+                    .template
+                    .as_ref()
+                    .map(|template| format_json(template, self.state.compact_json))
+                    .unwrap_or_else(|| "no template body".to_owned());
+
+                let detail = Paragraph::new(Text::raw(body))
+                    .block(ctx.style.block(false).title(selected.name.as_str()))
+                    .wrap(Wrap { trim: false });
+
+                ctx.frame.render_widget(detail, detail_area);
+            }
+        } else {
+            let not_found = Paragraph::new(Text::raw("not found"));
+
+            ctx.frame.render_widget(not_found, ctx.rect);
+        }
+    }
+
+    fn render_ilm_policies<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let policies = match self
+            .selected_cluster_name()
+            .and_then(|name| self.data.get_visible_ilm_policies(name))
+        {
+            Some(Some(policies)) => policies.collect::<Vec<_>>(),
+            Some(None) => {
+                let not_available = Paragraph::new(Text::raw("ILM not available"));
+
+                ctx.frame.render_widget(not_available, ctx.rect);
+                return;
+            }
+            None => {
+                let not_found = Paragraph::new(Text::raw("not found"));
+
+                ctx.frame.render_widget(not_found, ctx.rect);
+                return;
+            }
+        };
+
+        let (policy_list_area, phase_area) = {
+            let chunks = Layout::default()
+                .direction(Horizontal)
+                .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+                .split(ctx.rect);
+            (chunks[0], chunks[1])
+        };
+
+        let policy_list: Vec<ListItem> =
+            policies
+                .iter()
+                .enumerate()
+                .map(|(idx, (name, _))| {
+                    ListItem::new(Text::styled(
+                        name.as_str(),
+                        Style::default().add_modifier(ctx.style.selected_item_modifier(
+                            idx,
+                            self.state.ilm_policy_list_state.selected(),
+                        )),
+                    ))
+                })
+                .collect();
+
+        let policy_list = List::new(policy_list)
             .block(
                 ctx.style
-                    .block(self.state.focused == Some(ResourceList))
-                    .title(ctx.navigable_title("Elasticsearch")),
+                    .block(self.state.focused == Some(IlmPolicyList))
+                    .title(self.annotate_stale(ctx.navigable_title("Policy"), Ilm)),
             )
             .highlight_style(ctx.style.highlight_style())
             .highlight_symbol("> ");
 
-        ctx.frame.render_stateful_widget(
-            cluster_list,
-            cluster_list_area,
-            &mut self.state.cluster_list_state,
-        );
+        let selected = self
+            .state
+            .ilm_policy_list_state
+            .selected()
+            .and_then(|i| policies.get(i).copied());
 
         ctx.frame.render_stateful_widget(
-            resource_list,
-            resource_list_area,
-            &mut self.state.resource_list_state,
+            policy_list,
+            policy_list_area,
+            &mut self.state.ilm_policy_list_state,
         );
-    }
 
-    fn render_cluster<B>(&mut self, ctx: &mut ViewContext<B>)
-    where
-        B: tui::backend::Backend,
-    {
-        if let Some(health) = self
-            .selected_cluster_name()
-            .and_then(|name| self.data.get_cluster_health(name))
-        {
-            let cluster_health: Text = ClusterHealthFormatter(health, ctx.style).into();
-            let cluster_health_area = {
-                let chunks = Layout::default()
-                    .direction(Vertical)
-                    .constraints([
-                        Constraint::Length(
-                            cluster_health.height() as u16 + ctx.style.box_border_height(),
-                        ),
-                        Constraint::Percentage(100),
-                    ])
-                    .split(ctx.rect);
-                chunks[0]
-            };
+        if let Some((name, policy)) = selected {
+            let mut phases: Vec<_> = policy.policy.phases.iter().collect();
+            phases.sort_by_key(|(phase, _)| ilm_phase_order(phase));
 
-            let cluster_health = Paragraph::new(cluster_health)
-                .block(ctx.style.block(false).title("Cluster Health"))
-                .alignment(Alignment::Left);
+            let mut lines: Vec<Spans> = vec![
+                ctx.style.key_value_spans(
+                    "version",
+                    policy.version.map(|v| v.to_string()).unwrap_or_default(),
+                ),
+                ctx.style.key_value_spans(
+                    "modified_date",
+                    policy.modified_date.as_deref().unwrap_or_default(),
+                ),
+            ];
+            for (phase, detail) in phases {
+                lines.push(Spans::from(Span::styled(
+                    phase.as_str(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )));
+                lines.push(
+                    ctx.style
+                        .key_value_spans("min_age", detail.min_age.as_deref().unwrap_or("0ms")),
+                );
+                for (action, body) in detail.actions.iter() {
+                    lines.push(ctx.style.key_value_spans(action.as_str(), body));
+                }
+            }
 
-            ctx.frame.render_widget(cluster_health, cluster_health_area);
-        } else {
-            let not_found = Paragraph::new(Text::raw("not found"));
+            let phase_detail = Paragraph::new(lines)
+                .block(ctx.style.block(false).title(name.as_str()))
+                .wrap(Wrap { trim: false });
 
-            ctx.frame.render_widget(not_found, ctx.rect);
+            ctx.frame.render_widget(phase_detail, phase_area);
         }
     }
 
-    fn render_index<B>(&mut self, ctx: &mut ViewContext<B>)
+    fn render_snapshots<B>(&mut self, ctx: &mut ViewContext<B>)
     where
         B: tui::backend::Backend,
     {
-        if let Some(indices) = self
+        let repositories = match self
             .selected_cluster_name()
-            .and_then(|name| self.data.get_visible_indices(name))
+            .and_then(|name| self.data.get_repositories(name))
         {
-            let mut indices: Vec<&CatIndex> = indices.collect();
-            indices.sort_unstable_by_key(|index| &index.index);
+            Some(repositories) => repositories.collect::<Vec<_>>(),
+            None => {
+                let not_found = Paragraph::new(Text::raw("not found"));
+
+                ctx.frame.render_widget(not_found, ctx.rect);
+                return;
+            }
+        };
+
+        let (repository_list_area, snapshot_area) = {
+            let chunks = Layout::default()
+                .direction(Horizontal)
+                .constraints([Constraint::Percentage(25), Constraint::Percentage(75)].as_ref())
+                .split(ctx.rect);
+            (chunks[0], chunks[1])
+        };
 
-            let num_index = indices.len();
-            let max_index_width = indices
+        let repository_list: Vec<ListItem> =
+            repositories
                 .iter()
-                .map(|i| i.index.len() + 2)
-                .max()
-                .unwrap_or(10);
+                .enumerate()
+                .map(|(idx, (name, repository))| {
+                    ListItem::new(Text::styled(
+                        format!("{name} ({})", repository.repository_type),
+                        Style::default().add_modifier(ctx.style.selected_item_modifier(
+                            idx,
+                            self.state.repository_list_state.selected(),
+                        )),
+                    ))
+                })
+                .collect();
+
+        let repository_list =
+            List::new(repository_list)
+                .block(
+                    ctx.style
+                        .block(self.state.focused == Some(RepositoryList))
+                        .title(self.annotate_stale(
+                            ctx.navigable_title_with_key("Repository", 'o'),
+                            Snapshot,
+                        )),
+                )
+                .highlight_style(ctx.style.highlight_style())
+                .highlight_symbol("> ");
+
+        ctx.frame.render_stateful_widget(
+            repository_list,
+            repository_list_area,
+            &mut self.state.repository_list_state,
+        );
+
+        if let Some(snapshots) = self
+            .selected_cluster_name()
+            .zip(self.selected_repository_name())
+            .and_then(|(cluster, repository)| self.data.get_snapshots(cluster, repository))
+        {
+            let mut snapshots: Vec<_> = snapshots.collect();
+            // newest first.
+            snapshots.sort_unstable_by(|a, b| b.start_time.cmp(&a.start_time));
 
             let (header, column_constraints): (Vec<_>, Vec<_>) = [
-                ("  Index", Constraint::Length(max_index_width as u16)),
-                ("Health", Constraint::Length(6)),
-                ("Status", Constraint::Length(6)),
-                ("Primary", Constraint::Length(7)),
-                ("Replica", Constraint::Length(7)),
-                ("DocsCount", Constraint::Length(10)),
-                ("DocsDeleted", Constraint::Length(12)),
-                ("StoreSize", Constraint::Length(10)),
-                ("PrimaryStoreSize", Constraint::Length(18)),
-                ("Uuid", Constraint::Length(22)),
+                ("  Snapshot", Constraint::Percentage(40)),
+                ("State", Constraint::Length(12)),
+                ("StartTime", Constraint::Length(25)),
+                ("EndTime", Constraint::Length(25)),
+                ("Indices", Constraint::Length(7)),
             ]
             .into_iter()
             .map(|(h, c)| {
@@ -392,157 +3687,558 @@ impl ElasticsearchComponent {
 
             let header = Row::new(header).height(1).bottom_margin(0);
 
-            let rows = indices.iter().map(|index| {
+            let rows = snapshots.iter().map(|snapshot| {
                 let cells = vec![
                     Span::styled(
-                        "  ".to_owned() + index.index.as_str(),
+                        format!("  {}", snapshot.snapshot.as_str()),
                         Style::default().add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
-                        index.health.as_str(),
-                        Style::default().fg(health_color(index.health.as_str())),
+                        snapshot.state.as_str(),
+                        Style::default().fg(snapshot_state_color(snapshot.state.as_str())),
                     ),
-                    Span::styled(index.status.as_str(), Style::default()),
-                    Span::styled(index.pri.as_str(), Style::default()),
-                    Span::styled(index.rep.as_str(), Style::default()),
-                    Span::styled(index.docs_count.as_str(), Style::default().fg(Color::Cyan)),
-                    Span::styled(index.docs_deleted.as_str(), Style::default()),
                     Span::styled(
-                        humanize_str_bytes(index.store_size.as_str()),
+                        snapshot
+                            .start_time
+                            .as_deref()
+                            .map(|time| self.timestamp_format().rfc3339(time))
+                            .unwrap_or_default(),
                         Style::default(),
                     ),
                     Span::styled(
-                        humanize_str_bytes(index.pri_store_size.as_str()),
+                        snapshot
+                            .end_time
+                            .as_deref()
+                            .map(|time| self.timestamp_format().rfc3339(time))
+                            .unwrap_or_default(),
                         Style::default(),
                     ),
-                    Span::styled(index.uuid.as_str(), Style::default()),
+                    Span::styled(snapshot.indices.len().to_string(), Style::default()),
                 ]
                 .into_iter()
                 .map(Cell::from);
                 Row::new(cells).height(1)
             });
 
-            let indices_area = {
-                Layout::default()
-                    .direction(Vertical)
-                    .constraints([
-                        Constraint::Length(num_index as u16 + 1 + ctx.style.box_border_height()), // header
-                        Constraint::Percentage(100),
-                    ])
-                    .split(ctx.rect)[0]
-            };
-
-            let indices = Table::new(rows)
+            let snapshots = Table::new(rows)
                 .header(header)
                 .block(
                     ctx.style
-                        .block(self.state.focused == Some(IndexTable))
-                        .title(ctx.navigable_title("Index")),
+                        .block(self.state.focused == Some(SnapshotTable))
+                        .title(ctx.navigable_title("Snapshot")),
                 )
                 .highlight_style(ctx.style.highlight_style())
                 .highlight_symbol(">")
                 .widths(column_constraints.as_slice());
 
             ctx.frame.render_stateful_widget(
-                indices,
-                indices_area,
-                &mut self.state.index_table_state,
+                snapshots,
+                snapshot_area,
+                &mut self.state.snapshot_table_state,
             );
         } else {
             let not_found = Paragraph::new(Text::raw("not found"));
 
-            ctx.frame.render_widget(not_found, ctx.rect);
+            ctx.frame.render_widget(not_found, snapshot_area);
         }
     }
 
-    fn render_aliases<B>(&mut self, ctx: &mut ViewContext<B>)
+    fn render_settings<B>(&mut self, ctx: &mut ViewContext<B>)
     where
         B: tui::backend::Backend,
     {
-        if let Some(aliases) = self
+        let settings = match self
             .selected_cluster_name()
-            .and_then(|name| self.data.get_visible_aliases(name))
+            .and_then(|name| self.data.get_cluster_settings(name))
         {
-            let mut aliases: Vec<&CatAlias> = aliases.collect();
-            aliases.sort_unstable_by_key(|a| &a.alias);
+            Some(settings) => settings
+                .filter(|(_, key, _)| self.state.setting_filter.matches(key))
+                .collect::<Vec<_>>(),
+            None => {
+                let not_found = Paragraph::new(Text::raw("not found"));
 
-            let num_aliases = aliases.len();
-            let (_max_alias_width, _max_index_width) =
-                aliases.iter().fold((0, 0), |(max_alias, max_index), a| {
-                    (
-                        cmp::max(max_alias, a.alias.len()),
-                        cmp::max(max_index, a.index.len()),
-                    )
-                });
+                ctx.frame.render_widget(not_found, ctx.rect);
+                return;
+            }
+        };
+        let focused = self.state.focused == Some(SettingTable);
 
-            // TODO: handle too long alias name.
-            let (header, column_constraints): (Vec<_>, Vec<_>) = [
-                ("  Alias", Constraint::Percentage(30)),
-                ("Index", Constraint::Percentage(30)),
-                ("IsWrite", Constraint::Length(7)),
-                ("Filter", Constraint::Min(10)),
-                ("RoutingIndex", Constraint::Min(12)),
-                ("RoutingSearch", Constraint::Min(13)),
+        let (filter_area, table_area) = {
+            let chunks = Layout::default()
+                .direction(Vertical)
+                .constraints([
+                    Constraint::Length(1 + ctx.style.box_border_height()),
+                    Constraint::Percentage(100),
+                ])
+                .split(ctx.rect);
+            (chunks[0], chunks[1])
+        };
+
+        let setting_filter = &self.state.setting_filter;
+        let query = setting_filter.query();
+        let filter = Paragraph::new(Text::styled(
+            if focused {
+                format!("{query}_")
+            } else {
+                query.to_owned()
+            },
+            if setting_filter.is_valid() {
+                Style::default()
+            } else {
+                Style::default().fg(Color::Red)
+            },
+        ))
+        .block(
+            ctx.style
+                .block(focused)
+                .title(format!("Filter ({})", setting_filter.mode())),
+        );
+
+        ctx.frame.render_widget(filter, filter_area);
+
+        let (header, column_constraints): (Vec<_>, Vec<_>) = [
+            ("  Key", Constraint::Percentage(50)),
+            ("Value", Constraint::Percentage(40)),
+            ("Scope", Constraint::Length(10)),
+        ]
+        .into_iter()
+        .map(|(h, c)| {
+            (
+                Cell::from(h).style(Style::default().add_modifier(Modifier::DIM | Modifier::BOLD)),
+                c,
+            )
+        })
+        .unzip();
+
+        let header = Row::new(header).height(1).bottom_margin(0);
+
+        let rows = settings.iter().map(|(scope, key, value)| {
+            let cells = vec![
+                Span::styled(
+                    format!("  {key}"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(setting_value(value), Style::default().fg(Color::Cyan)),
+                Span::styled(*scope, Style::default().add_modifier(Modifier::DIM)),
             ]
             .into_iter()
-            .map(|(h, c)| {
-                (
-                    Cell::from(h)
-                        .style(Style::default().add_modifier(Modifier::DIM | Modifier::BOLD)),
-                    c,
-                )
+            .map(Cell::from);
+            Row::new(cells).height(1)
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(ctx.style.block(focused).title(
+                self.annotate_stale(ctx.navigable_title_with_key("Settings", 'g'), Settings),
+            ))
+            .highlight_style(ctx.style.highlight_style())
+            .highlight_symbol(">")
+            .widths(column_constraints.as_slice());
+
+        ctx.frame
+            .render_stateful_widget(table, table_area, &mut self.state.setting_table_state);
+    }
+
+    fn render_global_search<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let failed = self
+            .data
+            .get_global_search()
+            .map(|result| result.failed.as_slice())
+            .unwrap_or_default();
+
+        let (input_area, failed_area, result_area) = {
+            let chunks = Layout::default()
+                .direction(Vertical)
+                .constraints([
+                    Constraint::Length(1 + ctx.style.box_border_height()),
+                    Constraint::Length(failed.len() as u16),
+                    Constraint::Percentage(100),
+                ])
+                .split(ctx.rect);
+            (chunks[0], chunks[1], chunks[2])
+        };
+
+        let input = Paragraph::new(Text::raw(format!("{}_", self.state.global_search_query)))
+            .block(ctx.style.block(true).title("Search All Clusters"));
+
+        ctx.frame.render_widget(input, input_area);
+
+        let failed: Vec<Spans> = failed
+            .iter()
+            .map(|(cluster, reason)| {
+                Spans::from(Span::styled(
+                    format!("  {}: {reason}", self.state.redactor.cluster(cluster)),
+                    Style::default().fg(Color::Red),
+                ))
             })
-            .unzip();
+            .collect();
 
-            let header = Row::new(header).height(1).bottom_margin(0);
+        ctx.frame.render_widget(Paragraph::new(failed), failed_area);
 
-            let rows = aliases.iter().map(|alias| {
-                let cells = vec![
-                    Span::styled(
-                        format!("  {}", alias.alias.as_str()),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(alias.index.as_str(), Style::default()),
-                    Span::styled(alias.is_write_index.as_str(), Style::default()),
-                    Span::styled(alias.filter.as_str(), Style::default()),
-                    Span::styled(alias.routing_index.as_str(), Style::default()),
-                    Span::styled(alias.routing_search.as_str(), Style::default()),
-                ]
-                .into_iter()
-                .map(Cell::from);
-                Row::new(cells).height(1)
-            });
+        let result = match self.data.get_global_search() {
+            Some(result) => result,
+            None => return,
+        };
 
-            let aliases_area = {
-                Layout::default()
-                    .direction(Vertical)
-                    .constraints([
-                        Constraint::Length(num_aliases as u16 + 1 + ctx.style.box_border_height()),
-                        Constraint::Percentage(100),
-                    ])
-                    .split(ctx.rect)[0]
-            };
+        let (header, column_constraints): (Vec<_>, Vec<_>) = [
+            ("  Cluster", Constraint::Percentage(20)),
+            ("Index", Constraint::Percentage(40)),
+            ("Health", Constraint::Length(6)),
+            ("Status", Constraint::Length(6)),
+            ("DocsCount", Constraint::Length(10)),
+            ("StoreSize", Constraint::Length(10)),
+        ]
+        .into_iter()
+        .map(|(h, c)| {
+            (
+                Cell::from(h).style(Style::default().add_modifier(Modifier::DIM | Modifier::BOLD)),
+                c,
+            )
+        })
+        .unzip();
 
-            let aliases = Table::new(rows)
-                .header(header)
-                .block(
-                    ctx.style
-                        .block(self.state.focused == Some(AliasTable))
-                        .title(ctx.navigable_title("Alias")),
-                )
-                .highlight_style(ctx.style.highlight_style())
-                .highlight_symbol(">")
-                .widths(column_constraints.as_slice());
+        let header = Row::new(header).height(1).bottom_margin(0);
 
-            ctx.frame.render_stateful_widget(
-                aliases,
-                aliases_area,
-                &mut self.state.alias_table_state,
-            );
+        let rows = result.indices.iter().map(|(cluster, index)| {
+            let cells = vec![
+                Span::styled(
+                    format!("  {}", self.state.redactor.cluster(cluster)),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    self.state.redactor.index(&index.index),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    index.health.as_str(),
+                    Style::default().fg(health_color(index.health.as_str())),
+                ),
+                Span::styled(index.status.as_str(), Style::default()),
+                Span::styled(
+                    format_str_count(&index.docs_count, &self.thousands_separator),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    humanize_str_bytes(index.store_size.as_str(), self.byte_units),
+                    Style::default(),
+                ),
+            ]
+            .into_iter()
+            .map(Cell::from);
+            Row::new(cells).height(1)
+        });
+
+        let title = format!("\"{}\" {} indices", result.pattern, result.indices.len());
+        let table = Table::new(rows)
+            .header(header)
+            .block(ctx.style.block(false).title(title))
+            .highlight_style(ctx.style.highlight_style())
+            .highlight_symbol(">")
+            .widths(column_constraints.as_slice());
+
+        ctx.frame.render_stateful_widget(
+            table,
+            result_area,
+            &mut self.state.global_search_table_state,
+        );
+    }
+
+    fn render_raw_get<B>(&mut self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let (input_area, response_area) = {
+            let chunks = Layout::default()
+                .direction(Vertical)
+                .constraints([
+                    Constraint::Length(1 + ctx.style.box_border_height()),
+                    Constraint::Percentage(100),
+                ])
+                .split(ctx.rect);
+            (chunks[0], chunks[1])
+        };
+
+        let title = match self.selected_cluster_name() {
+            Some(cluster) => format!("GET {}", self.state.redactor.cluster(cluster)),
+            None => "GET (no cluster selected)".to_owned(),
+        };
+        let input = Paragraph::new(Text::raw(format!("/{}_", self.state.raw_get_path)))
+            .block(ctx.style.block(true).title(title));
+
+        ctx.frame.render_widget(input, input_area);
+
+        let result = match self.data.get_raw_response() {
+            Some(result) => result,
+            None => return,
+        };
+
+        let status_color = if (200..300).contains(&result.status) {
+            Color::Green
         } else {
-            let not_found = Paragraph::new(Text::raw("not found"));
+            Color::Red
+        };
+        let title = Spans::from(vec![
+            Span::raw(format!(
+                "{} /{} ",
+                self.state.redactor.cluster(&result.cluster_name),
+                result.path.trim_start_matches('/')
+            )),
+            Span::styled(result.status.to_string(), Style::default().fg(status_color)),
+        ]);
+        let response = Paragraph::new(Text::raw(result.body(self.state.compact_json)))
+            .block(ctx.style.block(false).title(title))
+            .scroll((self.state.raw_response_scroll, 0));
 
-            ctx.frame.render_widget(not_found, ctx.rect);
+        ctx.frame.render_widget(response, response_area);
+    }
+}
+
+/// Indices in order, grouped by the prefix if enabled with the indices of the expanded group
+/// listed. Indices without the prefix and groups of one index are kept as they are.
+fn index_rows<'a>(
+    indices: &[&'a CatIndex],
+    group: bool,
+    pattern: Option<&Regex>,
+    expanded: Option<&str>,
+) -> Vec<IndexRow<'a>> {
+    if !group {
+        return indices.iter().map(|index| IndexRow::Index(index)).collect();
+    }
+
+    // the group is placed at its first index to keep the order.
+    let mut groups: Vec<(Option<&str>, Vec<&CatIndex>)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for index in indices {
+        match index_prefix(&index.index, pattern) {
+            Some(prefix) => match positions.get(prefix) {
+                Some(&i) => groups[i].1.push(index),
+                None => {
+                    positions.insert(prefix, groups.len());
+                    groups.push((Some(prefix), vec![index]));
+                }
+            },
+            None => groups.push((None, vec![index])),
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (prefix, members) in groups {
+        match prefix {
+            Some(prefix) if members.len() > 1 => {
+                let is_expanded = expanded == Some(prefix);
+                rows.push(IndexRow::Group {
+                    prefix,
+                    members: members.clone(),
+                });
+                if is_expanded {
+                    rows.extend(members.into_iter().map(IndexRow::Member));
+                }
+            }
+            _ => rows.extend(members.into_iter().map(IndexRow::Index)),
+        }
+    }
+    rows
+}
+
+/// Sorted aliases, grouped by name if enabled with the indices of the expanded group listed.
+fn alias_rows<'a>(
+    aliases: impl Iterator<Item = &'a CatAlias>,
+    sort: AliasSort,
+    group: bool,
+    expanded: Option<&str>,
+) -> Vec<AliasRow<'a>> {
+    let mut aliases: Vec<&CatAlias> = aliases.collect();
+    match sort {
+        AliasSort::Alias => aliases.sort_unstable_by_key(|a| (&a.alias, &a.index)),
+        AliasSort::Index => aliases.sort_unstable_by_key(|a| (&a.index, &a.alias)),
+        AliasSort::WriteFirst => {
+            aliases.sort_unstable_by_key(|a| (a.is_write_index != "true", &a.alias, &a.index))
+        }
+    }
+    if !group {
+        return aliases.into_iter().map(AliasRow::Alias).collect();
+    }
+
+    let mut rows = Vec::new();
+    for (alias, members) in group_aliases(&aliases) {
+        if members.len() == 1 {
+            rows.push(AliasRow::Alias(members[0]));
+        } else if expanded == Some(alias) {
+            rows.push(AliasRow::Group {
+                alias,
+                members: members.clone(),
+            });
+            rows.extend(members.into_iter().map(AliasRow::Member));
+        } else {
+            rows.push(AliasRow::Group { alias, members });
+        }
+    }
+    rows
+}
+
+/// Shard count flagged by the index warnings is yellow, with the glyph if configured.
+fn shard_count_span(count: String, warned: bool, glyph: bool) -> Span<'static> {
+    match (warned, glyph) {
+        (false, _) => Span::raw(count),
+        (true, false) => Span::styled(count, Style::default().fg(Color::Yellow)),
+        (true, true) => Span::styled(format!("{count} ⚠"), Style::default().fg(Color::Yellow)),
+    }
+}
+
+/// Area of the popup over the top of the context, tall enough for the lines if possible.
+fn popup_area<B>(ctx: &ViewContext<B>, lines: usize) -> Rect
+where
+    B: tui::backend::Backend,
+{
+    let width = ctx.rect.width * 3 / 5;
+    let height = cmp::min(
+        lines as u16 + ctx.style.box_border_height(),
+        ctx.rect.height.saturating_sub(1),
+    );
+    Rect::new(
+        ctx.rect.x + (ctx.rect.width - width) / 2,
+        ctx.rect.y + 1,
+        width,
+        height,
+    )
+}
+
+/// Narrowest index table showing the column, the lower priority ones are hidden first.
+/// They are still shown in the expanded row.
+fn index_column_min_width(column: IndexColumn) -> u16 {
+    match column {
+        IndexColumn::Uuid => 140,
+        IndexColumn::PrimaryStoreSize => 120,
+        IndexColumn::DocsDeleted => 100,
+        _ => 0,
+    }
+}
+
+/// Cluster and resource whose data the request fetches.
+fn fetched_resource(req: &ElasticsearchRequestEvent) -> Option<(&str, ElasticsearchResourceKind)> {
+    use ElasticsearchRequestEvent::*;
+    match req {
+        FetchCluster { cluster_name } => Some((cluster_name, Cluster)),
+        FetchIndices { cluster_name, .. } => Some((cluster_name, Index)),
+        FetchNodes { cluster_name } => Some((cluster_name, Node)),
+        FetchAliases { cluster_name } => Some((cluster_name, Alias)),
+        FetchTemplates { cluster_name } => Some((cluster_name, Template)),
+        FetchIlmPolicies { cluster_name } => Some((cluster_name, Ilm)),
+        FetchRepositories { cluster_name } => Some((cluster_name, Snapshot)),
+        FetchClusterSettings { cluster_name } => Some((cluster_name, Settings)),
+        FetchTasks { cluster_name } => Some((cluster_name, Task)),
+        FetchSnapshots { .. }
+        | FetchSampleDocs { .. }
+        | FetchIndexSettings { .. }
+        | FetchIndexMappings { .. }
+        | FetchAllocationExplain { .. }
+        | RawRequest { .. }
+        | SearchAllClusters { .. }
+        | FetchAllClusterHealth => None,
+    }
+}
+
+/// Dim everything already rendered in the area.
+struct Dimmed;
+
+impl Widget for Dimmed {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().add_modifier(Modifier::DIM));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::{
+        client::elasticsearch::response::CatAlias,
+        config::{ElasticsearchCredential, ThemeConfig},
+        view::{style::Styled, ViewState},
+    };
+
+    fn component() -> ElasticsearchComponent {
+        let config = ElasticsearchConfig::builder()
+            .name("cluster-a".to_owned())
+            .endpoint("https://localhost:9200".parse().unwrap())
+            .credential(ElasticsearchCredential::builder().cloud_id(None).build())
+            .build();
+        ElasticsearchComponent::new(vec![config])
+    }
+
+    fn alias(i: usize) -> CatAlias {
+        CatAlias {
+            alias: format!("alias-{i:02}"),
+            filter: "-".to_owned(),
+            index: format!("index-{i:02}"),
+            is_write_index: "true".to_owned(),
+            routing_index: "-".to_owned(),
+            routing_search: "-".to_owned(),
+        }
+    }
+
+    #[test]
+    fn alias_table_scrolls_to_selection() {
+        let mut component = component();
+        let _ = component.update_api_response(ElasticsearchResponseEvent::Aliases {
+            cluster_name: "cluster-a".to_owned(),
+            response: (0..50).map(alias).collect(),
+        });
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 12)).unwrap();
+        let style = Styled::new(ThemeConfig::default());
+        let state = ViewState::new();
+        let mut render = |component: &mut ElasticsearchComponent| {
+            terminal
+                .draw(|frame| {
+                    let rect = frame.size();
+                    component.render_aliases(&mut ViewContext::new(frame, rect, &style, &state));
+                })
+                .unwrap()
+                .buffer
+                .content()
+                .iter()
+                .map(|cell| cell.symbol.as_str())
+                .collect::<String>()
+        };
+
+        let screen = render(&mut component);
+        assert!(screen.contains("alias-00"));
+        assert!(!screen.contains("alias-30"));
+
+        // well past the visible rows.
+        for _ in 0..30 {
+            let _ = component.navigate(AliasTable, Navigate::Down);
+        }
+        assert_eq!(component.state.alias_table_state.selected(), Some(30));
+
+        let screen = render(&mut component);
+        assert!(screen.contains("alias-30"));
+        assert!(!screen.contains("alias-00"));
+        assert!(!format!("{:?}", component.state.alias_table_state).contains("offset: 0,"));
+    }
+
+    #[test]
+    fn widen_column_after_narrowing_past_minimum() {
+        let mut component = component();
+        let column = component.visible_index_columns()[0];
+        component
+            .state
+            .index_column_default_widths
+            .insert(column, 10);
+        let width =
+            |component: &ElasticsearchComponent| 10 + component.state.index_column_widths[&column];
+
+        for _ in 0..20 {
+            component.adjust_column(IndexTable, ColumnAdjust::Narrow);
         }
+        assert_eq!(width(&component), MIN_COLUMN_WIDTH);
+
+        component.adjust_column(IndexTable, ColumnAdjust::Widen);
+        assert!(width(&component) > MIN_COLUMN_WIDTH);
     }
 }
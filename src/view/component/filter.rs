@@ -0,0 +1,299 @@
+use std::fmt::{self, Display};
+
+use regex::Regex;
+
+/// How the filter query is matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum FilterMode {
+    #[default]
+    Substring,
+    IgnoreCase,
+    Glob,
+    Regex,
+}
+
+impl FilterMode {
+    fn next(self) -> Self {
+        match self {
+            FilterMode::Substring => FilterMode::IgnoreCase,
+            FilterMode::IgnoreCase => FilterMode::Glob,
+            FilterMode::Glob => FilterMode::Regex,
+            FilterMode::Regex => FilterMode::Substring,
+        }
+    }
+}
+
+impl Display for FilterMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            FilterMode::Substring => "substring",
+            FilterMode::IgnoreCase => "ignore case",
+            FilterMode::Glob => "glob",
+            FilterMode::Regex => "regex",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Filter table rows by the text typed into the filter box.
+#[derive(Debug, Default)]
+pub(crate) struct TableFilter {
+    query: String,
+    mode: FilterMode,
+    /// Compiled query in regex mode, `None` if the query is not a valid regex.
+    regex: Option<Regex>,
+}
+
+impl TableFilter {
+    pub(crate) fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn with_mode(mut self, mode: FilterMode) -> Self {
+        self.mode = mode;
+        self.compile();
+        self
+    }
+
+    pub(crate) fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.compile();
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.query.pop();
+        self.compile();
+    }
+
+    pub(crate) fn cycle_mode(&mut self) {
+        self.mode = self.mode.next();
+        self.compile();
+    }
+
+    pub(crate) fn query(&self) -> &str {
+        self.query.as_str()
+    }
+
+    pub(crate) fn mode(&self) -> FilterMode {
+        self.mode
+    }
+
+    /// Invalid filter matches nothing until the query is fixed.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.mode != FilterMode::Regex || self.regex.is_some()
+    }
+
+    /// Empty query matches everything.
+    pub(crate) fn matches(&self, s: &str) -> bool {
+        if self.query.is_empty() {
+            return true;
+        }
+        match self.mode {
+            FilterMode::Substring => s.contains(self.query.as_str()),
+            FilterMode::IgnoreCase => s
+                .to_lowercase()
+                .contains(self.query.to_lowercase().as_str()),
+            FilterMode::Glob => glob_match(self.query.as_str(), s),
+            FilterMode::Regex => match self.regex.as_ref() {
+                Some(regex) => regex.is_match(s),
+                None => false,
+            },
+        }
+    }
+
+    /// Compile the query once on change instead of every match.
+    fn compile(&mut self) {
+        self.regex = match self.mode {
+            FilterMode::Regex => Regex::new(self.query.as_str()).ok(),
+            _ => None,
+        };
+    }
+}
+
+/// Score how well the query matches as a subsequence of the string, ignoring case.
+/// Higher is better, `None` if any query character is missing.
+pub(crate) fn fuzzy_score(query: &str, s: &str) -> Option<u32> {
+    let mut chars = s.chars().map(|c| c.to_ascii_lowercase()).enumerate();
+    let mut last_matched: Option<usize> = None;
+    let mut score = 0;
+
+    for q in query.chars().map(|c| c.to_ascii_lowercase()) {
+        let (i, _) = chars.find(|(_, c)| *c == q)?;
+        score += match last_matched {
+            // consecutive characters are likely a typed word.
+            Some(last) if last + 1 == i => 3,
+            None if i == 0 => 2,
+            _ => 1,
+        };
+        last_matched = Some(i);
+    }
+    Some(score)
+}
+
+/// Match the glob pattern where `*` matches any sequence of characters.
+fn glob_match(pattern: &str, s: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+
+    let (mut p, mut i) = (0, 0);
+    // position of the last `*` in the pattern and the position in the string it matched from.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while i < s.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, i));
+                p += 1;
+            }
+            Some(c) if *c == s[i] => {
+                p += 1;
+                i += 1;
+            }
+            _ => match backtrack {
+                // let the last `*` consume one more character.
+                Some((star, matched)) => {
+                    p = star + 1;
+                    i = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("*-2024", "logs-app-2024"));
+        assert!(glob_match("logs-*-2024", "logs-app-2024"));
+        assert!(glob_match("logs-*", "logs-app-2024"));
+        assert!(glob_match("*app*", "logs-app-2024"));
+        assert!(glob_match("logs-*-*", "logs-app-2024"));
+        assert!(glob_match("*", "logs-app-2024"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn glob_match_backtracks() {
+        // the first "-2024" is not the end.
+        assert!(glob_match("logs-*-2024", "logs-2024-app-2024"));
+        assert!(glob_match("*a*b", "aaab"));
+    }
+
+    #[test]
+    fn glob_match_empty_pattern() {
+        assert!(glob_match("", ""));
+        assert!(!glob_match("", "logs"));
+    }
+
+    #[test]
+    fn glob_match_literal() {
+        assert!(glob_match("logs-2024", "logs-2024"));
+        assert!(!glob_match("logs-2024", "logs-2024-01"));
+        assert!(!glob_match("logs-2024", "logs"));
+    }
+
+    #[test]
+    fn glob_match_no_match() {
+        assert!(!glob_match("logs-*-2024", "metrics-app-2024"));
+        assert!(!glob_match("logs-*-2024", "logs-app-2023"));
+        assert!(!glob_match("logs-*-2024", "logs-2024"));
+    }
+
+    #[test]
+    fn glob_filter() {
+        let filter = TableFilter::new("logs-*-2024").with_mode(FilterMode::Glob);
+        assert!(filter.matches("logs-app-2024"));
+        assert!(!filter.matches("metrics-app-2024"));
+        // the glob is anchored unlike the substring.
+        assert!(!filter.matches("old-logs-app-2024"));
+    }
+
+    #[test]
+    fn substring_filter() {
+        let filter = TableFilter::new("app");
+        assert_eq!(filter.mode(), FilterMode::Substring);
+        assert!(filter.matches("logs-app-2024"));
+        assert!(!filter.matches("logs-APP-2024"));
+    }
+
+    #[test]
+    fn ignore_case_filter() {
+        let filter = TableFilter::new("App").with_mode(FilterMode::IgnoreCase);
+        assert!(filter.matches("logs-APP-2024"));
+        assert!(filter.matches("logs-app-2024"));
+        assert!(!filter.matches("logs-api-2024"));
+    }
+
+    #[test]
+    fn regex_filter() {
+        let filter = TableFilter::new(r"^logs-\d+$").with_mode(FilterMode::Regex);
+        assert!(filter.is_valid());
+        assert!(filter.matches("logs-2024"));
+        assert!(!filter.matches("logs-app"));
+    }
+
+    #[test]
+    fn invalid_regex_matches_nothing() {
+        let mut filter = TableFilter::new("logs-(").with_mode(FilterMode::Regex);
+        assert!(!filter.is_valid());
+        assert!(!filter.matches("logs-("));
+
+        // valid again once the query is fixed.
+        filter.push(')');
+        assert!(filter.is_valid());
+        assert!(filter.matches("logs-"));
+    }
+
+    #[test]
+    fn cycle_mode_recompiles() {
+        let mut filter = TableFilter::new("logs-(");
+        assert!(filter.is_valid());
+        for _ in 0..3 {
+            filter.cycle_mode();
+        }
+        assert_eq!(filter.mode(), FilterMode::Regex);
+        assert!(!filter.is_valid());
+        filter.cycle_mode();
+        assert_eq!(filter.mode(), FilterMode::Substring);
+        assert!(filter.is_valid());
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = TableFilter::new("").with_mode(FilterMode::Glob);
+        assert!(filter.matches("logs-app-2024"));
+        assert!(filter.matches(""));
+    }
+
+    #[test]
+    fn fuzzy_score_subsequence() {
+        assert!(fuzzy_score("lga", "logs-app").is_some());
+        assert!(fuzzy_score("LOGS", "logs-app").is_some());
+        assert_eq!(fuzzy_score("", "logs-app"), Some(0));
+        assert_eq!(fuzzy_score("spa", "logs-app"), None);
+        assert_eq!(fuzzy_score("logs", "log"), None);
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_consecutive_from_start() {
+        // 2 for the first character, 3 for each consecutive one.
+        assert_eq!(fuzzy_score("log", "logs-app"), Some(8));
+        assert_eq!(fuzzy_score("lga", "logs-app"), Some(4));
+
+        let prefix = fuzzy_score("app", "app-logs").unwrap();
+        let inner = fuzzy_score("app", "logs-app").unwrap();
+        let scattered = fuzzy_score("app", "a-p-p").unwrap();
+        assert!(prefix > inner);
+        assert!(inner > scattered);
+    }
+}
@@ -1,4 +1,7 @@
-use std::sync::atomic::Ordering;
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
 
 use crossterm::event::KeyCode;
 use itertools::Itertools;
@@ -10,32 +13,81 @@ use tui::{
 
 use crate::{
     app::{TransportResult, TransportStats},
-    event::api::{elasticsearch::ElasticsearchResponseEvent, ResponseEvent},
-    view::{component::ResourceKind, ViewContext},
+    event::api::{
+        elasticsearch::{ElasticsearchResponseEvent, FanOutOutcome},
+        ResponseEvent,
+    },
+    view::{
+        component::{
+            elasticsearch::ElasticsearchComponentKind, redact::Redactor, ComponentKind,
+            ResourceKind,
+        },
+        style::Styled,
+        ViewContext,
+    },
+    HelpBar,
 };
 
+/// Interval to advance the in-flight spinner.
+pub(crate) const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
 pub(crate) struct HelpComponent {
     common_input_keys: Vec<(KeyCode, Span<'static>)>,
     elasticsearch_input_keys: Vec<(KeyCode, Span<'static>)>,
+    index_detail_input_keys: Vec<(KeyCode, Span<'static>)>,
+    allocation_detail_input_keys: Vec<(KeyCode, Span<'static>)>,
+    cluster_list_input_keys: Vec<(KeyCode, Span<'static>)>,
+    notification: Option<(String, Instant)>,
+    /// Spin while requests are in flight, otherwise show static text.
+    animations: bool,
+    started_at: Instant,
 }
 impl HelpComponent {
+    const NOTIFICATION_DURATION: Duration = Duration::from_secs(3);
+    const SPINNER_FRAMES: &'static [&'static str] =
+        &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
     pub(crate) fn new() -> Self {
         Self {
             common_input_keys: Self::common_key_spans(),
             elasticsearch_input_keys: Self::elasticsearch_key_spans(),
+            index_detail_input_keys: Self::index_detail_key_spans(),
+            allocation_detail_input_keys: Self::allocation_detail_key_spans(),
+            cluster_list_input_keys: Self::cluster_list_key_spans(),
+            notification: None,
+            animations: true,
+            started_at: Instant::now(),
         }
     }
 
+    pub(crate) fn with_animations(mut self, animations: bool) -> Self {
+        self.animations = animations;
+        self
+    }
+
+    pub(crate) fn animations(&self) -> bool {
+        self.animations
+    }
+
+    pub(crate) fn notify(&mut self, message: String) {
+        self.notification = Some((message, Instant::now()));
+    }
+
     fn common_key_spans() -> Vec<(KeyCode, Span<'static>)> {
         let s = Style::default().add_modifier(Modifier::DIM);
         vec![
             (KeyCode::Char('q'), Span::styled("q: Quit", s)),
             (KeyCode::Esc, Span::styled("esc: UnforcusTab", s)),
-            (KeyCode::Char('r'), Span::styled("r: Resource", s)),
+            (KeyCode::Char('r'), Span::styled("r: Resource Tab", s)),
             (KeyCode::Char('j'), Span::styled("j: ↓", s)),
             (KeyCode::Char('k'), Span::styled("k: ↑", s)),
             (KeyCode::Char('h'), Span::styled("h: ←", s)),
             (KeyCode::Char('l'), Span::styled("l: →", s)),
+            (KeyCode::Char('y'), Span::styled("y: Copy Error", s)),
+            (KeyCode::Char('o'), Span::styled("ctrl+o: Reload Config", s)),
+            (KeyCode::Char('e'), Span::styled("ctrl+e: Edit Config", s)),
+            (KeyCode::Char('v'), Span::styled("v: About", s)),
+            (KeyCode::Char('?'), Span::styled("?: Help Bar", s)),
         ]
     }
 
@@ -43,12 +95,113 @@ impl HelpComponent {
         let s = Style::default().add_modifier(Modifier::DIM);
         vec![
             (KeyCode::Char('c'), Span::styled("c: Cluster", s)),
+            (KeyCode::Char('d'), Span::styled("d: Dashboard", s)),
             (KeyCode::Char('e'), Span::styled("e: Elasticsearch", s)),
+            (KeyCode::Char('n'), Span::styled("n: Node", s)),
             (KeyCode::Char('i'), Span::styled("i: Index", s)),
             (KeyCode::Char('a'), Span::styled("a: Alias", s)),
+            (KeyCode::Char('t'), Span::styled("t: Template", s)),
+            (KeyCode::Char('p'), Span::styled("p: Policy", s)),
+            (KeyCode::Char('o'), Span::styled("o: Repository", s)),
+            (KeyCode::Char('s'), Span::styled("s: Snapshot", s)),
+            (KeyCode::Enter, Span::styled("enter: Detail", s)),
+            (KeyCode::Char('f'), Span::styled("f: Filter Preset", s)),
+            (KeyCode::Char('S'), Span::styled("S: Sort", s)),
+            (KeyCode::Char('x'), Span::styled("x: Expand Row", s)),
+            (KeyCode::Char('G'), Span::styled("G: Group Indices", s)),
+            (KeyCode::Char('<'), Span::styled("</>: Select Column", s)),
+            (KeyCode::Char(']'), Span::styled("[/]: Column Width", s)),
+            (KeyCode::Char('='), Span::styled("=: Reset Widths", s)),
+            (KeyCode::Char('w'), Span::styled("w: Wide Rows", s)),
+            (KeyCode::Char('.'), Span::styled(".: Hidden Indices", s)),
+            (KeyCode::Char('F'), Span::styled("F: Follow Health", s)),
+            (KeyCode::Char('R'), Span::styled("R: Redact Names", s)),
+            (KeyCode::Char('P'), Span::styled("P: Primaries/Total", s)),
+            (KeyCode::Char('B'), Span::styled("B: Raw Bytes", s)),
+            (KeyCode::Char('J'), Span::styled("J: Compact JSON", s)),
+            (KeyCode::Char('A'), Span::styled("A: Relative Time", s)),
+            (
+                KeyCode::Char('E'),
+                Span::styled("E: All Clusters Outcome", s),
+            ),
+            (KeyCode::Char('H'), Span::styled("H: Health Log", s)),
+            (KeyCode::Char('b'), Span::styled("b: Open Browser", s)),
+            (KeyCode::Char('C'), Span::styled("C: Copy Curl", s)),
+            (KeyCode::Char('Y'), Span::styled("Y: Copy Path", s)),
+            (KeyCode::Char('g'), Span::styled("g: Settings", s)),
+            (KeyCode::Char('T'), Span::styled("T: Task", s)),
+            (KeyCode::Char('/'), Span::styled("/: Search All", s)),
+            (KeyCode::Char(':'), Span::styled(":: Raw GET", s)),
+            (KeyCode::Char('p'), Span::styled("ctrl+p: Open Index", s)),
+            (KeyCode::Char('l'), Span::styled("ctrl+l: Reset View", s)),
+            (KeyCode::Char('b'), Span::styled("ctrl+b: Toggle Panel", s)),
+            // filter mode while typing in the filter box.
+            (
+                KeyCode::Tab,
+                Span::styled("tab: Next Component/Filter Mode", s),
+            ),
+            (
+                KeyCode::BackTab,
+                Span::styled("shift+tab: Previous Component", s),
+            ),
+        ]
+    }
+
+    fn index_detail_key_spans() -> Vec<(KeyCode, Span<'static>)> {
+        let s = Style::default().add_modifier(Modifier::DIM);
+        vec![
+            (KeyCode::Esc, Span::styled("esc: Leave Detail", s)),
+            (KeyCode::Char('j'), Span::styled("j: Scroll ↓", s)),
+            (KeyCode::Char('k'), Span::styled("k: Scroll ↑", s)),
+            (KeyCode::Char('m'), Span::styled("m: More Documents", s)),
+            (KeyCode::Char('b'), Span::styled("b: Open Browser", s)),
+        ]
+    }
+
+    fn allocation_detail_key_spans() -> Vec<(KeyCode, Span<'static>)> {
+        let s = Style::default().add_modifier(Modifier::DIM);
+        vec![
+            (KeyCode::Esc, Span::styled("esc: Leave Detail", s)),
+            (KeyCode::Char('j'), Span::styled("j: Scroll ↓", s)),
+            (KeyCode::Char('k'), Span::styled("k: Scroll ↑", s)),
+        ]
+    }
+
+    /// Letters and digits jump instead of acting as shortcuts.
+    fn cluster_list_key_spans() -> Vec<(KeyCode, Span<'static>)> {
+        let s = Style::default().add_modifier(Modifier::DIM);
+        vec![
+            (KeyCode::Null, Span::styled("a-z/0-9: Jump to Cluster", s)),
+            (KeyCode::Up, Span::styled("↑/↓: Move", s)),
+            (KeyCode::Esc, Span::styled("esc: Leave List", s)),
+            (KeyCode::Tab, Span::styled("tab: Next Component", s)),
+            (KeyCode::Char('c'), Span::styled("ctrl+c: Quit", s)),
         ]
     }
 
+    /// Keys available while the component is focused, instead of the resource keys.
+    fn focused_key_spans(&self, focused: ComponentKind) -> Option<&[(KeyCode, Span<'static>)]> {
+        match focused {
+            ComponentKind::Elasticsearch(ElasticsearchComponentKind::ClusterList) => {
+                Some(&self.cluster_list_input_keys)
+            }
+            _ => None,
+        }
+    }
+
+    /// Keys available while the component is entered, instead of the resource keys.
+    fn entered_key_spans(&self, entered: ComponentKind) -> Option<&[(KeyCode, Span<'static>)]> {
+        match entered {
+            ComponentKind::Elasticsearch(ElasticsearchComponentKind::IndexDetail) => {
+                Some(&self.index_detail_input_keys)
+            }
+            ComponentKind::Elasticsearch(ElasticsearchComponentKind::AllocationDetail) => {
+                Some(&self.allocation_detail_input_keys)
+            }
+            _ => None,
+        }
+    }
+
     /// Highlight key help according to input entered.
     fn highlight_key_spans<'a>(
         &self,
@@ -73,10 +226,21 @@ impl HelpComponent {
         Spans::from(spans)
     }
 
-    fn format_transport_stats(&self, stats: &TransportStats) -> Spans {
+    fn format_transport_stats(
+        &self,
+        stats: &TransportStats,
+        cluster_label: Option<Span<'static>>,
+        redactor: &Redactor,
+        style: &Styled,
+    ) -> Spans {
         let in_flight = stats.in_flight_requests.load(Ordering::Relaxed);
 
-        let mut s = Spans::from(vec![
+        let mut s = Spans::default();
+        if let Some(label) = cluster_label {
+            s.0.push(label);
+            s.0.push(Span::raw("  "));
+        }
+        s.0.extend(vec![
             Span::styled(
                 "in flight req: ",
                 Style::default().add_modifier(Modifier::DIM),
@@ -91,17 +255,50 @@ impl HelpComponent {
             ),
             Span::raw("  "),
         ]);
+        if in_flight > 0 {
+            s.0.push(Span::raw(self.loading_indicator()));
+            s.0.push(Span::raw("  "));
+        }
+        if let Some((attempt, max_attempts)) = stats.retrying() {
+            s.0.push(Span::styled(
+                format!("retry {attempt}/{max_attempts}"),
+                Style::default().fg(Color::Yellow),
+            ));
+            s.0.push(Span::raw("  "));
+        }
 
         if let Some(t) = stats.latest_transport() {
-            s.0.extend(format_transport(t).0.into_iter());
+            s.0.extend(format_transport(t, style, redactor).0.into_iter());
+        }
+
+        if let Some((message, _)) = self
+            .notification
+            .as_ref()
+            .filter(|(_, notified)| notified.elapsed() < Self::NOTIFICATION_DURATION)
+        {
+            s.0.push(Span::raw("  "));
+            s.0.push(Span::styled(
+                message.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
         }
         s
     }
 
+    fn loading_indicator(&self) -> &'static str {
+        if !self.animations {
+            return "[loading]";
+        }
+        let frame = self.started_at.elapsed().as_millis() / SPINNER_INTERVAL.as_millis();
+        Self::SPINNER_FRAMES[frame as usize % Self::SPINNER_FRAMES.len()]
+    }
+
     pub(crate) fn render<B>(
         &mut self,
         ctx: &mut ViewContext<B>,
         transport_stats: Option<&TransportStats>,
+        cluster_label: Option<Span<'static>>,
+        redactor: &Redactor,
     ) where
         B: tui::backend::Backend,
     {
@@ -109,21 +306,29 @@ impl HelpComponent {
 
         let mut lines = Vec::new();
 
-        lines.push(self.highlight_key_spans(self.common_input_keys.iter(), last_input_key_code));
+        if ctx.state.help_bar == HelpBar::Expanded {
+            lines
+                .push(self.highlight_key_spans(self.common_input_keys.iter(), last_input_key_code));
 
-        #[allow(clippy::single_match)]
-        match ctx.state.selected_resource {
-            Some(ResourceKind::Elasticsearch) => {
+            let keys = match ctx.state.entered_component {
+                Some(entered) => self.entered_key_spans(entered),
+                None => ctx
+                    .state
+                    .focused_component
+                    .and_then(|focused| self.focused_key_spans(focused)),
+            };
+            if let Some(keys) = keys {
+                lines.push(self.highlight_key_spans(keys.iter(), last_input_key_code));
+            } else if let Some(ResourceKind::Elasticsearch) = ctx.state.selected_resource {
                 lines.push(self.highlight_key_spans(
                     self.elasticsearch_input_keys.iter(),
                     last_input_key_code,
                 ));
             }
-            _ => (),
         }
 
         if let Some(stats) = transport_stats {
-            lines.push(self.format_transport_stats(stats));
+            lines.push(self.format_transport_stats(stats, cluster_label, redactor, ctx.style));
         }
 
         let help = Paragraph::new(lines)
@@ -138,34 +343,177 @@ impl HelpComponent {
     }
 }
 
-fn format_transport(t: TransportResult) -> Spans<'static> {
+/// One line for all clusters, partial failures are summarized instead of listed.
+fn fan_out_span(path: &str, outcome: &FanOutOutcome, style: Style) -> Span<'static> {
+    let style = if outcome.failed.is_empty() {
+        style
+    } else {
+        Style::default().fg(Color::Yellow)
+    };
+    Span::styled(
+        format!("elasticsearch all clusters {path} {}", outcome.summary()),
+        style,
+    )
+}
+
+/// Cluster and index names are replaced if redacted, as the cluster label next to it.
+fn format_transport(t: TransportResult, styled: &Styled, redactor: &Redactor) -> Spans<'static> {
     // need more improvement.
     let elapsed = t.elapsed();
+    let id = Span::styled(
+        format!("{} ", t.request_id),
+        Style::default().add_modifier(Modifier::DIM),
+    );
     match t.response {
         Ok(event) => {
             let ok = Style::default()
                 .fg(Color::Green)
                 .add_modifier(Modifier::DIM);
             let style = Style::default().add_modifier(Modifier::DIM);
-            let mut spans = Spans::from(vec![Span::styled("OK", ok), Span::raw(" ")]);
+            let mut spans = Spans::from(vec![Span::styled("OK", ok), Span::raw(" "), id]);
             let s = match event {
                 ResponseEvent::Elasticsearch(e) => match e {
                     ElasticsearchResponseEvent::ClusterHealth { cluster_name, .. } => Span::styled(
-                        format!("elasticsearch {cluster_name} /_cluster/health"),
+                        format!(
+                            "elasticsearch {} /_cluster/health",
+                            redactor.cluster(&cluster_name)
+                        ),
                         style,
                     ),
-                    ElasticsearchResponseEvent::Indices { cluster_name, .. } => {
-                        Span::styled(format!("elasticsearch {cluster_name} /_cat/indices"), style)
+                    ElasticsearchResponseEvent::Indices { cluster_name, .. } => Span::styled(
+                        format!(
+                            "elasticsearch {} /_cat/indices",
+                            redactor.cluster(&cluster_name)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::Nodes { cluster_name, .. } => Span::styled(
+                        format!(
+                            "elasticsearch {} /_cat/nodes",
+                            redactor.cluster(&cluster_name)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::Aliases { cluster_name, .. } => Span::styled(
+                        format!(
+                            "elasticsearch {} /_cat/aliases",
+                            redactor.cluster(&cluster_name)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::Templates { cluster_name, .. } => Span::styled(
+                        format!(
+                            "elasticsearch {} /_index_template",
+                            redactor.cluster(&cluster_name)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::IlmPolicies { cluster_name, .. } => Span::styled(
+                        format!(
+                            "elasticsearch {} /_ilm/policy",
+                            redactor.cluster(&cluster_name)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::Repositories { cluster_name, .. } => Span::styled(
+                        format!(
+                            "elasticsearch {} /_snapshot",
+                            redactor.cluster(&cluster_name)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::Snapshots {
+                        cluster_name,
+                        repository,
+                        ..
+                    } => Span::styled(
+                        format!(
+                            "elasticsearch {} /_snapshot/{repository}/_all",
+                            redactor.cluster(&cluster_name)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::SampleDocs {
+                        cluster_name,
+                        index,
+                        ..
+                    } => Span::styled(
+                        format!(
+                            "elasticsearch {} /{}/_search",
+                            redactor.cluster(&cluster_name),
+                            redactor.index(&index)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::IndexSettings {
+                        cluster_name,
+                        index,
+                        ..
+                    } => Span::styled(
+                        format!(
+                            "elasticsearch {} /{}/_settings",
+                            redactor.cluster(&cluster_name),
+                            redactor.index(&index)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::IndexMappings {
+                        cluster_name,
+                        index,
+                        ..
+                    } => Span::styled(
+                        format!(
+                            "elasticsearch {} /{}/_mapping",
+                            redactor.cluster(&cluster_name),
+                            redactor.index(&index)
+                        ),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::SearchAllClusters { outcome, .. } => {
+                        fan_out_span("/_cat/indices", &outcome, style)
+                    }
+                    ElasticsearchResponseEvent::AllClusterHealth { outcome, .. } => {
+                        fan_out_span("/_cluster/health", &outcome, style)
                     }
-                    ElasticsearchResponseEvent::Aliases { cluster_name, .. } => {
-                        Span::styled(format!("elasticsearch {cluster_name} /_cat/aliases"), style)
+                    ElasticsearchResponseEvent::ClusterSettings { cluster_name, .. } => {
+                        Span::styled(
+                            format!(
+                                "elasticsearch {} /_cluster/settings",
+                                redactor.cluster(&cluster_name)
+                            ),
+                            style,
+                        )
                     }
+                    ElasticsearchResponseEvent::AllocationExplain { cluster_name, .. } => {
+                        Span::styled(
+                            format!(
+                                "elasticsearch {} /_cluster/allocation/explain",
+                                redactor.cluster(&cluster_name)
+                            ),
+                            style,
+                        )
+                    }
+                    ElasticsearchResponseEvent::Tasks { cluster_name, .. } => Span::styled(
+                        format!("elasticsearch {} /_tasks", redactor.cluster(&cluster_name)),
+                        style,
+                    ),
+                    ElasticsearchResponseEvent::RawResponse {
+                        cluster_name, path, ..
+                    } => Span::styled(
+                        format!(
+                            "elasticsearch {} /{}",
+                            redactor.cluster(&cluster_name),
+                            redactor.path(path.trim_start_matches('/'))
+                        ),
+                        style,
+                    ),
                 },
             };
             spans.0.push(s);
-            spans
-                .0
-                .push(Span::styled(format!(" {}ms", elapsed.as_millis()), style));
+            spans.0.push(Span::styled(
+                format!(" {}ms", elapsed.as_millis()),
+                styled.latency_style(elapsed),
+            ));
             spans
         }
         Err(err) => {
@@ -173,6 +521,7 @@ fn format_transport(t: TransportResult) -> Spans<'static> {
             Spans::from(vec![
                 Span::styled("ERROR", err_style),
                 Span::raw("  "),
+                id,
                 Span::styled(
                     format!("{err}"),
                     Style::default().add_modifier(Modifier::DIM),
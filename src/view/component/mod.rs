@@ -5,7 +5,9 @@ use ascii::AsAsciiStr;
 use crate::view::component::elasticsearch::ElasticsearchComponentKind;
 
 pub(crate) mod elasticsearch;
+pub(crate) mod filter;
 pub(crate) mod help;
+pub(crate) mod redact;
 pub(crate) mod resource_tab;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +16,16 @@ pub(crate) enum ComponentKind {
     Elasticsearch(ElasticsearchComponentKind),
 }
 
+impl ComponentKind {
+    /// Whether the component takes character keys as text rather than shortcuts.
+    pub(crate) fn accepts_text(&self) -> bool {
+        match self {
+            ComponentKind::ResourceTab => false,
+            ComponentKind::Elasticsearch(component) => component.accepts_text(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ResourceKind {
     Elasticsearch,
@@ -0,0 +1,131 @@
+use std::{borrow::Cow, cell::RefCell, collections::HashMap};
+
+use itertools::Itertools;
+
+/// Replace cluster and index names with numbered placeholders for sharing screenshots.
+/// Only the displayed names are replaced, requests keep using the real ones.
+#[derive(Debug, Default)]
+pub(crate) struct Redactor {
+    enabled: bool,
+    /// Clusters are numbered in the config order.
+    clusters: Vec<String>,
+    /// Indices are numbered in the order first displayed and keep the number.
+    indices: RefCell<HashMap<String, usize>>,
+}
+
+impl Redactor {
+    pub(crate) fn new(clusters: Vec<String>) -> Self {
+        Self {
+            clusters,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn cluster<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if !self.enabled {
+            return Cow::Borrowed(name);
+        }
+        match self.clusters.iter().position(|cluster| cluster == name) {
+            Some(i) => Cow::Owned(format!("cluster-{}", i + 1)),
+            None => Cow::Borrowed("cluster-?"),
+        }
+    }
+
+    pub(crate) fn index<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if !self.enabled {
+            return Cow::Borrowed(name);
+        }
+        let mut indices = self.indices.borrow_mut();
+        let next = indices.len() + 1;
+        let n = *indices.entry(name.to_owned()).or_insert(next);
+        Cow::Owned(format!("index-{n}"))
+    }
+
+    /// Path of the api typed by the user like "logs-app/_search". Indices are the segments before
+    /// the api name starting with "_", or after the endpoint like "_cat/indices/logs-app".
+    pub(crate) fn path<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        if !self.enabled {
+            return Cow::Borrowed(path);
+        }
+        let (path, query) = match path.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (path, None),
+        };
+        let segments: Vec<&str> = path.split('/').collect();
+        let api = segments.iter().position(|segment| segment.starts_with('_'));
+        let mut redacted = segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| {
+                let is_index = match api {
+                    None => true,
+                    Some(api) if i < api => true,
+                    Some(api) => segments[api] == "_cat" && i >= api + 2,
+                };
+                if is_index && !segment.is_empty() {
+                    self.index(segment)
+                } else {
+                    Cow::Borrowed(*segment)
+                }
+            })
+            .join("/");
+        // values of the query may name indices too.
+        if query.is_some() {
+            redacted.push_str("?…");
+        }
+        Cow::Owned(redacted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> Redactor {
+        let mut redactor = Redactor::new(vec!["prod".to_owned(), "staging".to_owned()]);
+        redactor.toggle();
+        redactor
+    }
+
+    #[test]
+    fn disabled_keeps_names() {
+        let redactor = Redactor::new(vec!["prod".to_owned()]);
+        assert_eq!(redactor.cluster("prod"), "prod");
+        assert_eq!(redactor.index("logs-app"), "logs-app");
+        assert_eq!(
+            redactor.path("logs-app/_search?q=a"),
+            "logs-app/_search?q=a"
+        );
+    }
+
+    #[test]
+    fn names_are_numbered() {
+        let redactor = enabled();
+        assert_eq!(redactor.cluster("staging"), "cluster-2");
+        assert_eq!(redactor.cluster("unknown"), "cluster-?");
+        assert_eq!(redactor.index("logs-app"), "index-1");
+        assert_eq!(redactor.index("logs-web"), "index-2");
+        // keeps the number once displayed.
+        assert_eq!(redactor.index("logs-app"), "index-1");
+    }
+
+    #[test]
+    fn path_keeps_api_names() {
+        let redactor = enabled();
+        assert_eq!(redactor.path("_cat/health"), "_cat/health");
+        assert_eq!(redactor.path("logs-app/_search"), "index-1/_search");
+        assert_eq!(redactor.path("_cat/indices/logs-*"), "_cat/indices/index-2");
+        assert_eq!(
+            redactor.path("logs-app/_search?q=user:a"),
+            "index-1/_search?…"
+        );
+    }
+}
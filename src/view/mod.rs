@@ -1,4 +1,4 @@
-use std::{cell::Cell, sync::Arc};
+use std::{cell::Cell, cmp, collections::HashMap, sync::Arc, time::Duration};
 
 use ascii::AsAsciiStr;
 use component::resource_tab::ResourceTab;
@@ -6,8 +6,10 @@ use crossterm::event::KeyEvent;
 use tui::{
     layout::{Constraint, Direction::Vertical, Layout, Rect},
     text::Spans,
+    widgets::{Clear, Paragraph},
     Frame,
 };
+use url::Url;
 
 use crate::{
     app::TransportStats,
@@ -18,7 +20,7 @@ use crate::{
         },
         style::Styled,
     },
-    Config,
+    Config, HelpBar, IndexColumn, SessionState,
 };
 
 pub(crate) mod component;
@@ -36,7 +38,12 @@ pub(crate) struct View {
 pub(crate) struct ViewState {
     pub(crate) focused_component: Option<ComponentKind>,
     pub(crate) selected_resource: Option<ResourceKind>,
+    /// Component entered from the focused component, like the detail of a table row.
+    pub(crate) entered_component: Option<ComponentKind>,
     pub(crate) last_input_key: Cell<Option<KeyEvent>>,
+    /// Show the version and build info over the view.
+    pub(crate) show_about: bool,
+    pub(crate) help_bar: HelpBar,
 }
 
 impl ViewState {
@@ -44,19 +51,52 @@ impl ViewState {
         Self {
             focused_component: None,
             selected_resource: Some(ResourceKind::variants()[0]), // should query
+            entered_component: None,
             last_input_key: Cell::new(None),
+            show_about: false,
+            help_bar: HelpBar::default(),
         }
     }
 }
 
+/// Version of the elasticsearch client library, keep in sync with Cargo.toml.
+const ELASTICSEARCH_CLIENT_VERSION: &str = "8.5.0-alpha.1";
+
 impl View {
     pub(crate) fn new(config: Config) -> Self {
         Self {
             resource_tab: ResourceTab::new(),
-            elasticsearch: ElasticsearchComponent::new(config.elasticsearch.unwrap_or_default()),
-            help: HelpComponent::new(),
-            state: ViewState::new(),
-            style: Styled::new(),
+            elasticsearch: ElasticsearchComponent::new(config.elasticsearch.unwrap_or_default())
+                .with_default_cluster(config.default_cluster.as_deref())
+                .with_default_resource(config.default_resource.as_deref())
+                .with_index_filters(config.index_filters.unwrap_or_default())
+                .with_default_index_filter(config.default_index_filter)
+                .with_index_sort(config.index_default_sort.unwrap_or_default())
+                .with_show_hidden_indices(config.show_hidden_indices)
+                .with_byte_units(config.byte_units.unwrap_or_default())
+                .with_byte_display(config.byte_display.unwrap_or_default())
+                .with_thousands_separator(
+                    config.thousands_separator.unwrap_or_else(|| ",".to_owned()),
+                )
+                .with_index_columns(
+                    config
+                        .index_columns
+                        .unwrap_or_else(|| IndexColumn::ALL.to_vec()),
+                )
+                .with_merge_shard_columns(config.merge_shard_columns)
+                .with_alert_on_red(config.alert_on_red)
+                .with_prefetch_on_startup(config.prefetch_on_startup)
+                .with_refresh_on_focus(config.refresh_on_focus)
+                .with_group_aliases(config.group_aliases)
+                .with_group_indices(config.group_indices)
+                .with_index_group_pattern(config.index_group_pattern.as_deref())
+                .with_timezone(config.timezone.as_deref()),
+            help: HelpComponent::new().with_animations(config.animations.unwrap_or(true)),
+            state: ViewState {
+                help_bar: config.help_bar.unwrap_or_default(),
+                ..ViewState::new()
+            },
+            style: Styled::new(config.theme.unwrap_or_default()),
             transport_stats: None,
         }
     }
@@ -66,6 +106,11 @@ impl View {
         self
     }
 
+    pub(crate) fn with_build_failures(mut self, failures: HashMap<String, String>) -> Self {
+        self.elasticsearch = self.elasticsearch.with_build_failures(failures);
+        self
+    }
+
     /// Init view before into render loop.
     pub(crate) fn pre_render_loop(&mut self) -> Option<impl Iterator<Item = RequestEvent>> {
         #[allow(clippy::single_match)]
@@ -75,11 +120,27 @@ impl View {
         }
     }
 
+    /// Show the message in the help bar for a while.
+    pub(crate) fn notify(&mut self, message: impl Into<String>) {
+        self.help.notify(message.into());
+    }
+
+    pub(crate) fn toggle_about(&mut self) {
+        self.state.show_about = !self.state.show_about;
+    }
+
+    /// Shrink the help bar to the status line, hide it, then expand it again.
+    pub(crate) fn cycle_help_bar(&mut self) {
+        self.state.help_bar = self.state.help_bar.next();
+    }
+
     pub(crate) fn state(&self) -> &ViewState {
         &self.state
     }
 
     pub(crate) fn unfocus(&mut self) {
+        self.leave();
+
         if let Some(focused) = self.state.focused_component {
             match focused {
                 ComponentKind::ResourceTab => self.resource_tab.toggle_focus(false),
@@ -89,16 +150,316 @@ impl View {
         self.state.focused_component = None;
     }
 
-    pub(crate) fn focus(&mut self, component: ComponentKind) {
+    pub(crate) fn focus(
+        &mut self,
+        component: ComponentKind,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
         // disable current focus.
         self.unfocus();
 
+        self.state.focused_component = Some(component);
+
         match component {
-            ComponentKind::ResourceTab => self.resource_tab.toggle_focus(true),
+            ComponentKind::ResourceTab => {
+                self.resource_tab.toggle_focus(true);
+                None
+            }
             ComponentKind::Elasticsearch(component) => self.elasticsearch.focus(component),
         }
+    }
 
-        self.state.focused_component = Some(component);
+    /// Focus the next component of the selected resource, or the previous one if reversed.
+    pub(crate) fn focus_next(
+        &mut self,
+        reverse: bool,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        let mut cycle = vec![ComponentKind::ResourceTab];
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => cycle.extend(
+                self.elasticsearch
+                    .focus_cycle()
+                    .into_iter()
+                    .map(ComponentKind::Elasticsearch),
+            ),
+            _ => (),
+        }
+
+        let position = self
+            .state
+            .focused_component
+            .and_then(|focused| cycle.iter().position(|c| *c == focused));
+        let next = match (position, reverse) {
+            (None, false) => 0,
+            (None, true) => cycle.len() - 1,
+            (Some(i), false) => (i + 1) % cycle.len(),
+            (Some(i), true) => (i + cycle.len() - 1) % cycle.len(),
+        };
+        self.focus(cycle[next])
+    }
+
+    pub(crate) fn enter(
+        &mut self,
+        component: ComponentKind,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        match component {
+            ComponentKind::ResourceTab => None,
+            ComponentKind::Elasticsearch(component) => {
+                let entered = self.elasticsearch.enter(component)?;
+                self.state.entered_component = Some(ComponentKind::Elasticsearch(entered));
+                self.elasticsearch.fetch_entered()
+            }
+        }
+    }
+
+    pub(crate) fn leave(&mut self) {
+        if let Some(ComponentKind::Elasticsearch(_)) = self.state.entered_component {
+            self.elasticsearch.leave();
+        }
+        self.state.entered_component = None;
+    }
+
+    /// Collapse or expand the left panel of the selected resource.
+    pub(crate) fn toggle_left_panel(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => {
+                let collapsed = self.elasticsearch.toggle_left_panel();
+                // hidden component can not keep focus.
+                if collapsed
+                    && matches!(
+                        self.state.focused_component,
+                        Some(ComponentKind::Elasticsearch(component)) if component.is_left_panel()
+                    )
+                {
+                    self.unfocus();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Switch tables of the selected resource between one line and two lines per row.
+    pub(crate) fn toggle_density(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_density(),
+            _ => (),
+        }
+    }
+
+    /// Start or stop polling the cluster health.
+    pub(crate) fn toggle_follow(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_follow(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn is_following(&self) -> bool {
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.is_following(),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn highlight_remaining(&self) -> Option<Duration> {
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.highlight_remaining(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn follow_tick(&mut self) -> Option<impl Iterator<Item = RequestEvent>> {
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.follow_tick(),
+            _ => None,
+        }
+    }
+
+    /// Clusters whose health turned red since the last call.
+    pub(crate) fn take_turned_red(&mut self) -> Vec<String> {
+        self.elasticsearch.take_turned_red()
+    }
+
+    /// Whether to redraw periodically to animate the spinner.
+    pub(crate) fn animations(&self) -> bool {
+        self.help.animations()
+    }
+
+    pub(crate) fn session_state(&self) -> SessionState {
+        SessionState {
+            help_bar: Some(self.state.help_bar),
+            ..self.elasticsearch.session_state()
+        }
+    }
+
+    /// Fetch the next page of the entered component.
+    pub(crate) fn load_more(&mut self) -> Option<impl Iterator<Item = RequestEvent>> {
+        match self.elasticsearch.load_more() {
+            Ok(requests) => requests,
+            Err(reason) => {
+                self.notify(reason);
+                None
+            }
+        }
+    }
+
+    /// Clear the filter and the sort of the current resource.
+    pub(crate) fn reset(&mut self) -> Option<impl Iterator<Item = RequestEvent>> {
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.reset(),
+            _ => None,
+        }
+    }
+
+    /// Replace cluster and index names with placeholders, or show them again.
+    pub(crate) fn toggle_redact(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_redact(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn toggle_fan_out(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_fan_out(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn toggle_health_log(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_health_log(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn toggle_size_scope(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_size_scope(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn cycle_byte_display(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.cycle_byte_display(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn toggle_compact_json(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_compact_json(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn toggle_relative_timestamps(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_relative_timestamps(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn toggle_index_groups(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_index_groups(),
+            _ => (),
+        }
+    }
+
+    /// Show or hide dot-prefixed system indices.
+    pub(crate) fn toggle_hidden_indices(&mut self) {
+        #[allow(clippy::single_match)]
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.toggle_hidden_indices(),
+            _ => (),
+        }
+    }
+
+    /// Url to open the selected item in a browser.
+    pub(crate) fn selected_url(&self) -> Option<Url> {
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.selected_index_url(),
+            _ => None,
+        }
+    }
+
+    /// Curl command of the request fetching the selected resource.
+    pub(crate) fn selected_curl(&self) -> Option<String> {
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.selected_curl(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn selected_path(&self) -> Option<String> {
+        match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => self.elasticsearch.selected_path(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn toggle_expand(
+        &mut self,
+        component: ComponentKind,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        match component {
+            ComponentKind::ResourceTab => None,
+            ComponentKind::Elasticsearch(component) => self.elasticsearch.toggle_expand(component),
+        }
+    }
+
+    pub(crate) fn adjust_column(&mut self, component: ComponentKind, adjust: ColumnAdjust) {
+        match component {
+            ComponentKind::ResourceTab => (),
+            ComponentKind::Elasticsearch(component) => {
+                self.elasticsearch.adjust_column(component, adjust)
+            }
+        }
+    }
+
+    pub(crate) fn cycle_sort(&mut self, component: ComponentKind) {
+        match component {
+            ComponentKind::ResourceTab => (),
+            ComponentKind::Elasticsearch(component) => self.elasticsearch.cycle_sort(component),
+        }
+    }
+
+    pub(crate) fn cycle_filter_preset(
+        &mut self,
+        component: ComponentKind,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        match component {
+            ComponentKind::ResourceTab => None,
+            ComponentKind::Elasticsearch(component) => {
+                self.elasticsearch.cycle_filter_preset(component)
+            }
+        }
+    }
+
+    pub(crate) fn jump_to_initial(
+        &mut self,
+        component: ComponentKind,
+        initial: char,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        match component {
+            ComponentKind::ResourceTab => None,
+            ComponentKind::Elasticsearch(component) => {
+                self.elasticsearch.jump_to_initial(component, initial)
+            }
+        }
     }
 
     pub(crate) fn navigate_component(
@@ -118,28 +479,59 @@ impl View {
         }
     }
 
-    pub(crate) fn update_api_response(&mut self, res: ResponseEvent) {
+    pub(crate) fn edit_component(
+        &mut self,
+        component: ComponentKind,
+        edit: TextEdit,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
+        match component {
+            ComponentKind::ResourceTab => None,
+            ComponentKind::Elasticsearch(component) => {
+                let requests = self.elasticsearch.edit(component, edit);
+                // submitting the index palette moves the focus to the index table.
+                self.state.focused_component = self
+                    .elasticsearch
+                    .focused()
+                    .map(ComponentKind::Elasticsearch);
+                requests
+            }
+        }
+    }
+
+    pub(crate) fn update_api_response(
+        &mut self,
+        res: ResponseEvent,
+    ) -> Option<impl Iterator<Item = RequestEvent>> {
         match res {
             ResponseEvent::Elasticsearch(res) => self.elasticsearch.update_api_response(res),
         }
     }
 
+    /// Record the reason the request failed, to be shown instead of the data.
+    pub(crate) fn update_api_error(&mut self, req: RequestEvent, reason: String) {
+        match req {
+            RequestEvent::Elasticsearch(req) => self.elasticsearch.update_api_error(req, reason),
+        }
+    }
+
     pub(crate) fn render<B>(&mut self, frame: &mut Frame<B>, rect: Rect)
     where
         B: tui::backend::Backend,
     {
         let (resource_tab_area, resource_area, help_area) = {
+            // the help bar has only the top border.
+            let (resource, help) = match self.state.help_bar {
+                HelpBar::Expanded => (
+                    Constraint::Percentage(88),
+                    Constraint::Max(3 + self.style.box_border_height()),
+                ),
+                HelpBar::Minimal => (Constraint::Min(0), Constraint::Length(2)),
+                HelpBar::Hidden => (Constraint::Min(0), Constraint::Length(0)),
+            };
             let chunks = Layout::default()
                 .direction(Vertical)
                 .margin(0)
-                .constraints(
-                    [
-                        Constraint::Length(3),
-                        Constraint::Percentage(88),
-                        Constraint::Max(3 + self.style.box_border_height()),
-                    ]
-                    .as_ref(),
-                )
+                .constraints([Constraint::Length(3), resource, help].as_ref())
                 .split(rect);
             (chunks[0], chunks[1], chunks[2])
         };
@@ -148,14 +540,77 @@ impl View {
 
         self.resource_tab.render(&mut ctx);
 
-        #[allow(clippy::single_match)]
-        match self.resource_tab.selected_resource() {
-            ResourceKind::Elasticsearch => self.elasticsearch.render(ctx.with(resource_area)),
-            _ => (),
+        let cluster_label = match self.resource_tab.selected_resource() {
+            ResourceKind::Elasticsearch => {
+                self.elasticsearch.render(ctx.with(resource_area));
+                self.elasticsearch.selected_cluster_label()
+            }
+            _ => None,
+        };
+
+        if self.state.help_bar != HelpBar::Hidden {
+            self.help.render(
+                ctx.with(help_area),
+                self.transport_stats.as_deref(),
+                cluster_label,
+                self.elasticsearch.redactor(),
+            );
+        }
+
+        if self.state.show_about {
+            self.render_about(ctx.with(resource_area));
         }
+    }
+
+    /// Render what is running over the resource, to be pasted into bug reports.
+    fn render_about<B>(&self, ctx: &mut ViewContext<B>)
+    where
+        B: tui::backend::Backend,
+    {
+        let profile = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        let lines = vec![
+            self.style
+                .key_value_spans("  version", env!("CARGO_PKG_VERSION")),
+            self.style
+                .key_value_spans("  elasticsearch client", ELASTICSEARCH_CLIENT_VERSION),
+            self.style
+                .key_value_spans("  clusters", self.elasticsearch.cluster_count()),
+            self.style.key_value_spans(
+                "  build",
+                format!(
+                    "{}-{} {profile}",
+                    std::env::consts::OS,
+                    std::env::consts::ARCH
+                ),
+            ),
+        ];
 
-        self.help
-            .render(ctx.with(help_area), self.transport_stats.as_deref())
+        let area = {
+            let width = cmp::min(ctx.rect.width, 50);
+            let height = cmp::min(
+                lines.len() as u16 + self.style.box_border_height(),
+                ctx.rect.height,
+            );
+            Rect::new(
+                ctx.rect.x + (ctx.rect.width - width) / 2,
+                ctx.rect.y + 1,
+                width,
+                height,
+            )
+        };
+        ctx.frame.render_widget(Clear, area);
+        ctx.frame.render_widget(
+            Paragraph::new(lines).block(
+                self.style
+                    .block(false)
+                    .title(concat!(env!("CARGO_PKG_NAME"), " (v/esc to close)")),
+            ),
+            area,
+        );
     }
 }
 
@@ -205,6 +660,27 @@ impl Navigate {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextEdit {
+    Push(char),
+    Pop,
+    Submit,
+    /// Switch how the text is matched.
+    CycleMode,
+}
+
+/// Change the width of a table column by keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColumnAdjust {
+    /// Make the previous column the active one.
+    Prev,
+    Next,
+    Widen,
+    Narrow,
+    /// Back to the default widths.
+    Reset,
+}
+
 trait ApplyNavigate {
     fn apply(&mut self, navigate: Navigate, len: usize);
 }
@@ -267,13 +743,20 @@ where
     }
 
     fn navigable_title<'a>(&self, title: &'a str) -> Spans<'a> {
+        match title.as_ascii_str().ok().and_then(|s| s.get_ascii(0)) {
+            Some(first) => {
+                self.navigable_title_with_key(title, first.to_ascii_lowercase().as_char())
+            }
+            None => Spans::from(title),
+        }
+    }
+
+    /// Title with the key to focus, for titles whose first letter is already taken.
+    fn navigable_title_with_key<'a>(&self, title: &'a str, key: char) -> Spans<'a> {
         if self.state.focused_component.is_some() {
             Spans::from(title)
         } else {
-            match title.as_ascii_str().ok().and_then(|s| s.get_ascii(0)) {
-                Some(first) => Spans::from(format!("{title}({})", first.to_ascii_lowercase())),
-                None => Spans::from(title),
-            }
+            Spans::from(format!("{title}({key})"))
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
 
 use tui::{
     style::{Color, Modifier, Style},
@@ -6,11 +6,33 @@ use tui::{
     widgets::{Block, BorderType, Borders},
 };
 
-pub(crate) struct Styled {}
+use crate::config::ThemeConfig;
+
+pub(crate) struct Styled {
+    /// Sizes from which store size is white, yellow and red.
+    store_size_thresholds: [u64; 3],
+    /// Percents from which node memory usage is yellow and red.
+    node_memory_thresholds: [u8; 2],
+    /// Percents of deleted docs from which the deleted count is yellow and red.
+    deleted_docs_thresholds: [u8; 2],
+    /// Milliseconds from which the request latency is white, yellow and red.
+    latency_thresholds: [u64; 3],
+}
 
 impl Styled {
-    pub(super) fn new() -> Self {
-        Self {}
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    pub(super) fn new(theme: ThemeConfig) -> Self {
+        Self {
+            store_size_thresholds: theme.store_size_thresholds.unwrap_or([
+                Self::GIB,
+                10 * Self::GIB,
+                100 * Self::GIB,
+            ]),
+            node_memory_thresholds: theme.node_memory_thresholds.unwrap_or([75, 90]),
+            deleted_docs_thresholds: theme.deleted_docs_thresholds.unwrap_or([20, 40]),
+            latency_thresholds: theme.latency_thresholds.unwrap_or([200, 1000, 3000]),
+        }
     }
 
     pub(super) fn block(&self, focused: bool) -> Block {
@@ -55,6 +77,20 @@ impl Styled {
     }
 
     pub(super) fn key_value_spans<'a, K, V>(&self, key: K, value: V) -> Spans<'a>
+    where
+        K: Into<Cow<'a, str>>,
+        V: ToString,
+    {
+        self.key_value_spans_with_style(key, value, Style::default().fg(Color::Yellow))
+    }
+
+    /// Key value spans whose value is styled to draw attention.
+    pub(super) fn key_value_spans_with_style<'a, K, V>(
+        &self,
+        key: K,
+        value: V,
+        value_style: Style,
+    ) -> Spans<'a>
     where
         K: Into<Cow<'a, str>>,
         V: ToString,
@@ -72,7 +108,94 @@ impl Styled {
                     .fg(Color::LightBlue)
                     .add_modifier(Modifier::DIM),
             ),
-            Span::styled(value.to_string(), Style::default().fg(Color::Yellow)),
+            Span::styled(value.to_string(), value_style),
         ])
     }
+
+    /// Style to draw attention to indices with many deleted docs, which waste space until merged.
+    pub(super) fn deleted_docs_style(&self, docs_count: &str, docs_deleted: &str) -> Style {
+        let [yellow, red] = self.deleted_docs_thresholds;
+        let (count, deleted) = match (docs_count.parse::<u64>(), docs_deleted.parse::<u64>()) {
+            (Ok(count), Ok(deleted)) => (count, deleted),
+            // closed indices have no counts.
+            _ => return Style::default(),
+        };
+        let total = count.saturating_add(deleted);
+        if total == 0 {
+            return Style::default();
+        }
+        let percent = deleted as f64 / total as f64 * 100.0;
+        if percent >= f64::from(red) {
+            Style::default().fg(Color::Red)
+        } else if percent >= f64::from(yellow) {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        }
+    }
+
+    /// Style to draw attention to memory pressured nodes.
+    pub(super) fn node_memory_style(&self, percent: &str) -> Style {
+        let [yellow, red] = self.node_memory_thresholds;
+        match percent.parse::<u8>() {
+            Ok(n) if n >= red => Style::default().fg(Color::Red),
+            Ok(n) if n >= yellow => Style::default().fg(Color::Yellow),
+            Ok(_) => Style::default().fg(Color::Green),
+            Err(_) => Style::default(),
+        }
+    }
+
+    /// Style to tell how responsive the cluster is, apart from the success of the request.
+    pub(super) fn latency_style(&self, elapsed: Duration) -> Style {
+        let [white, yellow, red] = self.latency_thresholds;
+        match elapsed.as_millis() {
+            n if n >= u128::from(red) => Style::default().fg(Color::Red),
+            n if n >= u128::from(yellow) => Style::default().fg(Color::Yellow),
+            n if n >= u128::from(white) => Style::default().fg(Color::White),
+            _ => Style::default().fg(Color::Green),
+        }
+    }
+
+    /// Style to draw attention to large store size.
+    pub(super) fn store_size_style(&self, bytes: &str) -> Style {
+        let [white, yellow, red] = self.store_size_thresholds;
+        match bytes.parse::<u64>() {
+            Ok(n) if n >= red => Style::default().fg(Color::Red),
+            Ok(n) if n >= yellow => Style::default().fg(Color::Yellow),
+            Ok(n) if n >= white => Style::default().fg(Color::White),
+            Ok(_) => Style::default().add_modifier(Modifier::DIM),
+            Err(_) => Style::default(),
+        }
+    }
+}
+
+/// Parse the color name like "red", "lightblue" or hex "#rrggbb" written in config.
+pub(super) fn parse_color(s: &str) -> Option<Color> {
+    if let Some(hex) = s.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let rgb = u32::from_str_radix(hex, 16).ok()?;
+        return Some(Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8));
+    }
+    let color = match s.to_lowercase().replace(['_', '-', ' '], "").as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    };
+    Some(color)
 }